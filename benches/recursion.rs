@@ -0,0 +1,226 @@
+//! The recursive-scope workload: each recursive function call attaches a new integer element,
+//! and an iterator cumulates all elements; compared for several containers and N = 8 .. 512.
+//!
+//! ```commandline
+//! cargo bench --features chunk
+//! ```
+#[macro_use]
+extern crate criterion;
+extern crate im;
+extern crate rpds;
+extern crate seq;
+
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, BenchmarkId, Criterion};
+use seq::Seq;
+use seq::empty;
+use seq::inline::InlineSeq;
+use std::collections::LinkedList;
+use std::hint::black_box;
+
+/// The numbers of elements benchmarked
+const SIZES: [u32; 7] = [8, 16, 32, 64, 128, 256, 512];
+
+// Returns cumulation of  0, 0+1, 0+1+2, 0+1+2+3, ... 0+1+2+..+(N-1)
+fn sum_of_sums(n: u32) -> u32
+{
+    let cumulated = (n * (n + 1) * ((2 * n) + 1) / 6) + ((n * (n + 1)) / 2);
+    cumulated / 2
+}
+
+// Recursive function, adding an element and cumulate the sums, until N-1 is reached.
+fn recurs_stack_seq(s: &Seq<u32>, cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        let ext_s = Seq::ConsRef(cnt, s);
+
+        let sum = ext_s.into_iter().sum::<u32>();
+        sum + recurs_stack_seq(&ext_s, cnt + 1, n)
+    } else {
+        0
+    }
+}
+
+// Recursive function, adding a boxed element and cumulate the sums, until N-1 is reached.
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+fn recurs_heap_seq(s: Seq<'static, u32>, cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        let ext_s = Seq::ConsOwn(cnt, Box::new(s));
+
+        let sum = ext_s.into_iter().sum::<u32>();
+        sum + recurs_heap_seq(ext_s, cnt + 1, n)
+    } else {
+        0
+    }
+}
+
+// Recursive function, adding an element to the chunk, linking the chunk once full, and
+// cumulate the sums, until N-1 is reached.
+#[cfg(feature = "chunk")]
+fn recurs_stack_chunk(s: &Seq<u32>, mut chunk: seq::chunk::Chunk<u32>, cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        let _ = chunk.push(cnt);
+
+        let sum = chunk.as_slice().iter().chain(s).sum::<u32>();
+        if chunk.is_full() {
+            let ext_s = Seq::ConsChunk(chunk, s);
+            sum + recurs_stack_chunk(&ext_s, seq::chunk::Chunk::new(), cnt + 1, n)
+        } else {
+            sum + recurs_stack_chunk(s, chunk, cnt + 1, n)
+        }
+    } else {
+        0
+    }
+}
+
+// Recursive function, adding an element and cumulate the sums, until N-1 is reached.
+fn recurs_stack_list(l: &mut LinkedList<u32>, cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        l.push_back(cnt);
+        let sum = l.iter().sum::<u32>();
+        let r = sum + recurs_stack_list(l, cnt + 1, n);
+        l.pop_back();
+        r
+    } else {
+        0
+    }
+}
+
+// Recursive function, adding an element and cumulate the sums, until N-1 is reached.
+fn recurs_stack_vec(v: &mut Vec<u32>, cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        v.push(cnt);
+        let sum = v.iter().sum::<u32>();
+        let r = sum + recurs_stack_vec(v, cnt + 1, n);
+        v.truncate(cnt as usize);
+        r
+    } else {
+        0
+    }
+}
+
+// Recursive function, adding an element and cumulate the sums, until N-1 is reached.
+fn recurs_stack_im_vector(v: &mut im::Vector<u32>, cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        v.push_back(cnt);
+        let sum = v.iter().sum::<u32>();
+        let r = sum + recurs_stack_im_vector(v, cnt + 1, n);
+        v.pop_back();
+        r
+    } else {
+        0
+    }
+}
+
+// Recursive function, adding an element and cumulate the sums, until N-1 is reached.
+fn recurs_stack_rpds_list(l: &rpds::List<u32>, cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        let ext_l = l.push_front(cnt);
+
+        let sum = ext_l.iter().sum::<u32>();
+        sum + recurs_stack_rpds_list(&ext_l, cnt + 1, n)
+    } else {
+        0
+    }
+}
+
+// Recursive function, adding an element and cumulate the sums, until N-1 is reached.
+fn recurs_stack_array(a: &mut [u32], cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        a[cnt as usize] = cnt;
+
+        let sum = a[..(cnt + 1) as usize].iter().sum::<u32>();
+        sum + recurs_stack_array(a, cnt + 1, n)
+    } else {
+        0
+    }
+}
+
+// Recursive function, adding an element to the uninitialized scratch buffer and cumulate the
+// sums, until N-1 is reached.
+fn recurs_stack_inline<const N: usize>(a: &mut InlineSeq<u32, N>, cnt: u32, n: u32) -> u32 {
+    if cnt < n {
+        let _ = a.push(cnt);
+
+        let sum = a.as_slice().iter().sum::<u32>();
+        let r = sum + recurs_stack_inline(a, cnt + 1, n);
+        a.pop();
+        r
+    } else {
+        0
+    }
+}
+
+fn bench_recursive_scope(c: &mut Criterion) {
+    let mut group = c.benchmark_group("recursive_scope");
+    for n in SIZES.iter().cloned() {
+        group.bench_with_input(BenchmarkId::new("seq", n), &n, |b, &n| b.iter(|| {
+            let sum = recurs_stack_seq(empty(), 0, black_box(n));
+            assert_eq!(sum, sum_of_sums(n - 1));
+            sum
+        }));
+        #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+        group.bench_with_input(BenchmarkId::new("seq_owned", n), &n, |b, &n| b.iter(|| {
+            let sum = recurs_heap_seq(Seq::Empty, 0, black_box(n));
+            assert_eq!(sum, sum_of_sums(n - 1));
+            sum
+        }));
+        #[cfg(feature = "chunk")]
+        group.bench_with_input(BenchmarkId::new("seq_chunk", n), &n, |b, &n| b.iter(|| {
+            let sum = recurs_stack_chunk(empty(), seq::chunk::Chunk::new(), 0, black_box(n));
+            assert_eq!(sum, sum_of_sums(n - 1));
+            sum
+        }));
+        group.bench_with_input(BenchmarkId::new("vec", n), &n, |b, &n| b.iter(|| {
+            let mut v = Vec::new();
+            let sum = recurs_stack_vec(&mut v, 0, black_box(n));
+            assert_eq!(sum, sum_of_sums(n - 1));
+            sum
+        }));
+        group.bench_with_input(BenchmarkId::new("list", n), &n, |b, &n| b.iter(|| {
+            let mut l = LinkedList::new();
+            let sum = recurs_stack_list(&mut l, 0, black_box(n));
+            assert_eq!(sum, sum_of_sums(n - 1));
+            sum
+        }));
+        group.bench_with_input(BenchmarkId::new("im_vector", n), &n, |b, &n| b.iter(|| {
+            let mut v = im::Vector::new();
+            let sum = recurs_stack_im_vector(&mut v, 0, black_box(n));
+            assert_eq!(sum, sum_of_sums(n - 1));
+            sum
+        }));
+        group.bench_with_input(BenchmarkId::new("rpds_list", n), &n, |b, &n| b.iter(|| {
+            let sum = recurs_stack_rpds_list(&rpds::List::new(), 0, black_box(n));
+            assert_eq!(sum, sum_of_sums(n - 1));
+            sum
+        }));
+    }
+    bench_arrays::<8>(&mut group);
+    bench_arrays::<16>(&mut group);
+    bench_arrays::<32>(&mut group);
+    bench_arrays::<64>(&mut group);
+    bench_arrays::<128>(&mut group);
+    bench_arrays::<256>(&mut group);
+    bench_arrays::<512>(&mut group);
+    group.finish();
+}
+
+// The arrays are sized at compile time, holding N elements; the scratch buffer is left
+// uninitialized, the elements are written as they are attached
+fn bench_arrays<const N: usize>(group: &mut BenchmarkGroup<WallTime>) {
+    let n = N as u32;
+    group.bench_function(BenchmarkId::new("array", n), |b| b.iter(|| {
+        let mut a = [0u32; N];
+        let sum = recurs_stack_array(&mut a, 0, black_box(n));
+        assert_eq!(sum, sum_of_sums(n - 1));
+        sum
+    }));
+    group.bench_function(BenchmarkId::new("uninit", n), |b| b.iter(|| {
+        let mut a = InlineSeq::<u32, N>::new();
+        let sum = recurs_stack_inline(&mut a, 0, black_box(n));
+        assert_eq!(sum, sum_of_sums(n - 1));
+        sum
+    }));
+}
+
+criterion_group!(benches, bench_recursive_scope);
+criterion_main!(benches);
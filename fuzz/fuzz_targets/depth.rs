@@ -0,0 +1,36 @@
+//! Builds long sequences, up to a depth bound, and checks that dropping, cloning, comparing
+//! and hashing them does not overflow the stack.
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate seq;
+
+use seq::Seq;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// The maximum number of elements of a sequence
+const MAX_DEPTH: usize = 1 << 20;
+
+fuzz_target!(|input: (u32, u8, bool)| {
+    let (depth, value, differ) = input;
+    let depth = depth as usize % MAX_DEPTH;
+
+    let s: Seq<u8> = std::iter::repeat_n(value, depth).collect();
+    let tail = s.clone();
+    let mut t = Seq::ConsRef(value, &tail);
+    if differ {
+        t = Seq::ConsOwn(value.wrapping_add(1), Box::new(t));
+    }
+
+    assert_eq!(s.len(), depth);
+    assert_eq!(s, tail);
+    assert_ne!(s, t);
+    assert!(s < t || differ && value == u8::MAX);
+
+    let mut hasher = DefaultHasher::new();
+    t.hash(&mut hasher);
+    let _ = hasher.finish();
+    drop(t);
+    drop(s);
+});
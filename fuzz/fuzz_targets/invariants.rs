@@ -0,0 +1,62 @@
+//! Builds sequences of mixed nodes, sharing a common tail, and checks the invariants of
+//! length, equality, ordering and hashing.
+#![no_main]
+// reflexivity and symmetry are checked by comparing identical operands
+#![allow(clippy::eq_op)]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate seq;
+
+use seq::Seq;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn hash_of(seq: &Seq<u8>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seq.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Attaches the elements onto `tail`, referenced by the bottom element, the last element
+/// becoming the head
+fn attach<'a>(elements: &[u8], tail: &'a Seq<'a, u8>) -> Seq<'a, u8> {
+    match elements.split_first() {
+        None => Seq::ConsSlice(&[], tail),
+        Some((ft, rest)) => {
+            let mut seq = Seq::ConsRef(*ft, tail);
+            for ft in rest {
+                seq = Seq::ConsOwn(*ft, Box::new(seq));
+            }
+            seq
+        }
+    }
+}
+
+fn check(s: &Seq<u8>, t: &Seq<u8>) {
+    assert_eq!(s.len(), s.into_iter().count());
+    assert_eq!(s.is_empty(), s.into_iter().next().is_none());
+    assert_eq!(s, s);
+    assert_eq!(s == t, t == s);
+    assert_eq!(s.cmp(t), t.cmp(s).reverse());
+    assert_eq!(s == t, s.cmp(t) == Ordering::Equal);
+    if s == t {
+        assert_eq!(hash_of(s), hash_of(t));
+    }
+    assert_eq!(*s, s.to_vec().iter().rev().cloned().collect::<Vec<_>>()[..]);
+}
+
+fuzz_target!(|input: (Seq<'static, u8>, Vec<u8>, Vec<u8>, Vec<u8>)| {
+    let (base, slice, a, b) = input;
+    let shared = Seq::ConsSlice(&slice, &base);
+    let s = attach(&a, &shared);
+    let t = attach(&b, &shared);
+
+    for x in [&base, &shared, &s, &t] {
+        for y in [&base, &shared, &s, &t] {
+            check(x, y);
+        }
+    }
+    assert_eq!(s.len(), a.len() + slice.len() + base.len());
+    assert_eq!(s.clone(), s);
+});
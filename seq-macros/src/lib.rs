@@ -0,0 +1,306 @@
+//! Procedural macros of the crate `seq`, re-exported by `seq` with feature `macros`.
+//!
+//! The macro `static_seqs!` generates long static sequences at compile time from a literal
+//! list, an inclusive integer range or a file included. Each node is placed into a static item
+//! of its own, so the length of a sequence is not bound by the recursion limit of the compiler.
+//! The sequences declared by a single invocation share their nodes, whenever they have a tail
+//! in common; a declared sequence being the tail of another one is that very node.
+//!
+//! Use the macro via its re-export `seq::static_seqs!`, which passes the path of crate `seq`
+//! on to the macro.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, Ident, LitInt, LitStr, Token, Type, Visibility};
+
+/// The number of elements a range may yield at most
+const MAX_RANGE_LEN: u128 = 1 << 20;
+
+/// The invocation, the path of crate `seq` followed by the declarations
+struct Input {
+    krate: TokenStream2,
+    decls: Vec<Decl>,
+}
+
+/// A single declaration `static NAME: TYPE = SOURCE;`
+struct Decl {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    ty: Type,
+    elems: Vec<TokenStream2>,
+    tracked: Option<PathBuf>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut krate = TokenStream2::new();
+        while !input.peek(Token![;]) {
+            let tt: proc_macro2::TokenTree = input.parse()?;
+            tt.to_tokens(&mut krate);
+        }
+        input.parse::<Token![;]>()?;
+        let mut decls = Vec::new();
+        while !input.is_empty() {
+            decls.push(input.parse()?);
+        }
+        Ok(Input { krate, decls })
+    }
+}
+
+impl Parse for Decl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![static]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let (elems, tracked) = parse_source(input)?;
+        input.parse::<Token![;]>()?;
+        Ok(Decl { attrs, vis, ident, ty, elems, tracked })
+    }
+}
+
+/// Parses the source of the elements, returning the elements in order of the list, the last
+/// one becoming the head; and the path of the file included, if any
+fn parse_source(input: ParseStream) -> syn::Result<(Vec<TokenStream2>, Option<PathBuf>)> {
+    if input.peek(syn::token::Bracket) {
+        let content;
+        syn::bracketed!(content in input);
+        let list = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+        return Ok((list.into_iter().map(|e| e.into_token_stream()).collect(), None));
+    }
+    if input.peek(Ident) && input.peek2(Token![!]) {
+        let mac: Ident = input.parse()?;
+        if mac != "include" {
+            return Err(syn::Error::new(mac.span(), "expected `include!(\"path\")`"));
+        }
+        input.parse::<Token![!]>()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let lit: LitStr = content.parse()?;
+        return include_file(&lit);
+    }
+    let start = Bound::parse(input)?;
+    input.parse::<Token![..=]>()?;
+    let end = Bound::parse(input)?;
+    range(&start, &end).map(|elems| (elems, None))
+}
+
+/// A bound of a range, an integer literal, negated or not
+struct Bound {
+    value: i128,
+    lit: LitInt,
+}
+
+impl Parse for Bound {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let negated = input.parse::<Option<Token![-]>>()?.is_some();
+        let lit: LitInt = input.parse()?;
+        let magnitude: u128 = lit.base10_parse()?;
+        let value = if negated {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        };
+        match value {
+            Some(value) => Ok(Bound { value, lit }),
+            None => Err(syn::Error::new(lit.span(), "range bound out of range of i128")),
+        }
+    }
+}
+
+/// Returns the elements of the inclusive range, each one typed by the suffix of `start`
+fn range(start: &Bound, end: &Bound) -> syn::Result<Vec<TokenStream2>> {
+    let (from, to) = (start.value, end.value);
+    if to >= from && to.abs_diff(from) >= MAX_RANGE_LEN {
+        return Err(syn::Error::new(end.lit.span(), "range exceeds the maximum number of elements"));
+    }
+    let suffix = start.lit.suffix();
+    Ok((from..=to).map(|i| {
+        let lit = LitInt::new(&format!("{}{}", i.unsigned_abs(), suffix), Span::call_site());
+        if i < 0 { quote! { -#lit } } else { lit.into_token_stream() }
+    }).collect())
+}
+
+/// Reads the comma separated elements of the file, its path relative to the manifest directory
+/// of the crate being compiled
+fn include_file(lit: &LitStr) -> syn::Result<(Vec<TokenStream2>, Option<PathBuf>)> {
+    let mut path = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    path.push(lit.value());
+    let text = std::fs::read_to_string(&path).map_err(|err| {
+        syn::Error::new(lit.span(), format!("reading {}: {}", path.display(), err))
+    })?;
+    let list = syn::parse::Parser::parse_str(Punctuated::<Expr, Token![,]>::parse_terminated, &text)
+        .map_err(|err| syn::Error::new(lit.span(), format!("parsing {}: {}", path.display(), err)))?;
+    Ok((list.into_iter().map(|e| e.into_token_stream()).collect(), Some(path)))
+}
+
+/// A node of the generated sequences, the index of the node beneath and the element
+struct Node {
+    ty: usize,
+    tail: Option<usize>,
+    elem: TokenStream2,
+}
+
+/// Generates static sequences from literal lists, inclusive integer ranges or included files,
+/// sharing common tails. The first tokens up to `;` are the path of crate `seq`.
+#[proc_macro]
+pub fn static_seqs(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as Input);
+    expand(input).into()
+}
+
+fn expand(input: Input) -> TokenStream2 {
+    let krate = &input.krate;
+    let prefix = match input.decls.first() {
+        Some(decl) => decl.ident.to_string(),
+        None => return TokenStream2::new(),
+    };
+
+    let mut types: Vec<&Type> = Vec::new();
+    let mut type_index: HashMap<String, usize> = HashMap::new();
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut node_index: HashMap<(usize, Option<usize>, String), usize> = HashMap::new();
+    let mut heads: Vec<Option<usize>> = Vec::new();
+
+    for decl in &input.decls {
+        let ty = *type_index.entry(decl.ty.to_token_stream().to_string()).or_insert_with(|| {
+            types.push(&decl.ty);
+            types.len() - 1
+        });
+        let mut tail = None;
+        for elem in &decl.elems {
+            let key = (ty, tail, elem.to_string());
+            let next = nodes.len();
+            let idx = *node_index.entry(key).or_insert(next);
+            if idx == next {
+                nodes.push(Node { ty, tail, elem: elem.clone() });
+            }
+            tail = Some(idx);
+        }
+        heads.push(tail);
+    }
+
+    // the first declaration of a node is the static item holding it, unless configured away
+    let mut owners: Vec<Option<&Ident>> = vec![None; nodes.len()];
+    for (decl, head) in input.decls.iter().zip(&heads) {
+        let conditional = decl.attrs.iter().any(|attr| attr.path().is_ident("cfg"));
+        if let Some(i) = *head {
+            if owners[i].is_none() && !conditional {
+                owners[i] = Some(&decl.ident);
+            }
+        }
+    }
+
+    let mut items = TokenStream2::new();
+    for (decl, head) in input.decls.iter().zip(&heads) {
+        let (attrs, vis, ident, ty) = (&decl.attrs, &decl.vis, &decl.ident, &decl.ty);
+        let value = match *head {
+            Some(i) => {
+                let elem = &nodes[i].elem;
+                let rt = node_ref(krate, &prefix, &owners, nodes[i].tail);
+                quote! { #krate::Seq::ConsRef(#elem, #rt) }
+            }
+            None => quote! { #krate::Seq::Empty },
+        };
+        items.extend(quote! { #(#attrs)* #vis static #ident: #ty = #value; });
+        if let Some(ref path) = decl.tracked {
+            // rebuilding whenever the file included changes
+            let path = path.to_string_lossy();
+            items.extend(quote! { const _: &[u8] = include_bytes!(#path); });
+        }
+    }
+
+    for (i, node) in nodes.iter().enumerate() {
+        if owners[i].is_some() {
+            continue;
+        }
+        let name = node_name(&prefix, i);
+        let ty = types[node.ty];
+        let elem = &node.elem;
+        let rt = node_ref(krate, &prefix, &owners, node.tail);
+        items.extend(quote! {
+            #[doc(hidden)]
+            static #name: #ty = #krate::Seq::ConsRef(#elem, #rt);
+        });
+    }
+    items
+}
+
+/// Returns the name of the hidden static item holding the node `i`
+fn node_name(prefix: &str, i: usize) -> Ident {
+    format_ident!("__{}_NODE_{}", prefix, i, span = Span::call_site())
+}
+
+/// Returns the reference onto the node `tail`, being a declared item or a hidden one, or onto
+/// the empty sequence
+fn node_ref(krate: &TokenStream2, prefix: &str, owners: &[Option<&Ident>], tail: Option<usize>)
+            -> TokenStream2 {
+    match tail {
+        Some(i) => match owners[i] {
+            Some(ident) => quote! { &#ident },
+            None => {
+                let name = node_name(prefix, i);
+                quote! { &#name }
+            }
+        },
+        None => quote! { &#krate::Seq::Empty },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, Input};
+
+    fn expand_str(input: &str) -> syn::Result<String> {
+        syn::parse_str::<Input>(input).map(|input| expand(input).to_string())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(expand_str("seq; static A: Seq<u32> = 0..4;").is_err());
+        assert!(expand_str("seq; static A: Seq<u32> = 0..=;").is_err());
+        assert!(expand_str("seq; static A: Seq<u32> = [0, 1]").is_err());
+        assert!(expand_str("seq; static A: Seq<u32> = concat!(\"0, 1\");").is_err());
+        assert!(expand_str("seq; static A: Seq<u32> = include!(\"no/such/file.txt\");").is_err());
+        assert!(expand_str("seq; static A: Seq<u64> = 0..=1048576;").is_err());
+    }
+
+    #[test]
+    fn test_negative_range() {
+        let out = expand_str("seq; static A: Seq<i8> = -2..=1i8;").unwrap();
+        assert!(out.contains("ConsRef (1 , & __A_NODE_2)"));
+        assert!(out.contains("ConsRef (- 2 , & seq :: Seq :: Empty)"));
+        assert_eq!(out.matches("static").count(), 4);
+        assert!(expand_str("seq; static A: Seq<i32> = 3..=-3;").unwrap().contains("Seq :: Empty"));
+    }
+
+    #[test]
+    fn test_shared_nodes() {
+        let out = expand_str("seq;
+            static A: Seq<u32> = [0, 1, 2, 3];
+            static B: Seq<u32> = [0, 1, 2];
+            static C: Seq<u32> = [0, 1, 2];
+            static D: Seq<u64> = [0, 1];").unwrap();
+        // B holds the node of 2, shared by A and C; D is of another type
+        assert!(out.contains("static A : Seq < u32 > = seq :: Seq :: ConsRef (3 , & B)"));
+        assert!(out.contains("static C : Seq < u32 > = seq :: Seq :: ConsRef (2 , & __A_NODE_1)"));
+        assert!(out.contains("static D : Seq < u64 > = seq :: Seq :: ConsRef (1 , & __A_NODE_4)"));
+        assert_eq!(out.matches("static").count(), 4 + 3);
+    }
+}
@@ -0,0 +1,44 @@
+//! Structure-aware fuzzing input with crate `arbitrary`, enabled by feature `arbitrary`.
+//!
+//! An arbitrary sequence is made of boxed `ConsOwn` elements, taken from the unstructured
+//! input the way vectors are; the element taken last becomes the head.
+
+extern crate arbitrary;
+
+use self::arbitrary::{Arbitrary, Result, Unstructured};
+use super::Seq;
+
+/// Sequences of boxed elements, read like vectors of the elements
+impl<'f, T: Arbitrary<'f>> Arbitrary<'f> for Seq<'static, T> {
+    fn arbitrary(u: &mut Unstructured<'f>) -> Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'f>) -> Result<Self> {
+        u.arbitrary_take_rest_iter()?.collect()
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(<usize as Arbitrary>::size_hint(depth), (0, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::arbitrary::{Arbitrary, Unstructured};
+    use super::super::Seq;
+
+    #[test]
+    fn test_arbitrary() {
+        let bytes = [1u8, 7, 1, 8, 0];
+        let s = Seq::<u8>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert_eq!(s, [8, 7][..]);
+        assert!(matches!(s, Seq::ConsOwn(..)));
+
+        let t = Seq::<u8>::arbitrary_take_rest(Unstructured::new(&[3, 2, 1])).unwrap();
+        assert!(t.len() <= 3);
+
+        let e = Seq::<u32>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(e.is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! Zero-copy archives of sequences with rkyv, enabled by feature `rkyv`.
+//!
+//! A sequence is archived flat, as `ArchivedVec` of its elements in chronological order: the
+//! bottom element comes first, the head last. Inverse to the order of iteration, this is the
+//! order of a `ConsSlice` node, so [`archived_seq`] views an archive, such as a memory-mapped
+//! file, as sequence without copying.
+//!
+//! Deserializing an archive creates a sequence of boxed `ConsOwn` elements.
+//!
+//! # Example
+//! ```rust
+//! # extern crate rkyv;
+//! use seq::Seq;
+//! use seq::archive::{archived_seq, ArchivedSeq};
+//! use rkyv::rancor::Error;
+//!
+//! let s1 = Seq::ConsRef(1u32, seq::empty());
+//! let s2 = Seq::ConsRef(2u32, &s1);
+//! let bytes = rkyv::to_bytes::<Error>(&s2).unwrap();
+//!
+//! let archived = rkyv::access::<ArchivedSeq<u32>, Error>(&bytes).unwrap();
+//! assert_eq!(archived_seq::<u32>(archived).head().map(|x| x.to_native()), Some(2));
+//! ```
+//! [`archived_seq`]: fn.archived_seq.html
+
+use alloc::vec::Vec;
+#[cfg(not(feature = "lite-seq"))]
+use alloc::boxed::Box;
+
+use rkyv::rancor::Fallible;
+use rkyv::ser::{Allocator, Writer};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Place, Serialize};
+#[cfg(not(feature = "lite-seq"))]
+use rkyv::Deserialize;
+
+use super::Seq;
+
+/// The archived sequence of elements of type `T`, the bottom element first
+pub type ArchivedSeq<T> = ArchivedVec<<T as Archive>::Archived>;
+
+/// The elements are archived in chronological order, the head last
+impl<'a, T: Archive> Archive for Seq<'a, T> {
+    type Archived = ArchivedSeq<T>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedVec::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+/// The element references are buffered to archive them in chronological order
+impl<'a, T, S> Serialize<S> for Seq<'a, T>
+    where T: Serialize<S>, S: Fallible + Allocator + Writer + ?Sized
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let buffer: Vec<&T> = self.into_iter().collect();
+        ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _>(buffer.iter().rev().copied(),
+                                                                   serializer)
+    }
+}
+
+/// An archive is deserialized into boxed elements, the last archived element being the head
+#[cfg(not(feature = "lite-seq"))]
+impl<'a, T, D> Deserialize<Seq<'a, T>, D> for ArchivedSeq<T>
+    where T: Archive + 'a, T::Archived: Deserialize<T, D>, D: Fallible + ?Sized
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<Seq<'a, T>, D::Error> {
+        let mut seq = Seq::Empty;
+        for ft in self.iter() {
+            seq = Seq::ConsOwn(ft.deserialize(deserializer)?, Box::new(seq));
+        }
+        Ok(seq)
+    }
+}
+
+/// Returns the archived elements as sequence of a single `ConsSlice` node, without copying
+pub fn archived_seq<T: Archive>(archived: &ArchivedSeq<T>) -> Seq<'_, T::Archived> {
+    Seq::from(archived.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::empty;
+    use super::{archived_seq, ArchivedSeq};
+    use rkyv::rancor::Error;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_archive() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        let bytes = rkyv::to_bytes::<Error>(&s).unwrap();
+        let archived = rkyv::access::<ArchivedSeq<u32>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived[0], 1);
+
+        let view = archived_seq::<u32>(archived);
+        assert_eq!(view.len(), 3);
+        assert_eq!(view.into_iter().map(|x| x.to_native()).collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        let bytes = rkyv::to_bytes::<Error>(empty::<u32>()).unwrap();
+        let archived = rkyv::access::<ArchivedSeq<u32>, Error>(&bytes).unwrap();
+        assert!(archived_seq::<u32>(archived).is_empty());
+    }
+
+    #[cfg(not(feature = "lite-seq"))]
+    #[test]
+    fn test_archive_round_trip() {
+        use super::super::Seq;
+
+        let data = [4u32, 5];
+        seqdef!(s; empty() => 1u32, 2, 3);
+        let sliced = Seq::ConsSlice(&data, &s);
+
+        let bytes = rkyv::to_bytes::<Error>(&sliced).unwrap();
+        let archived = rkyv::access::<ArchivedSeq<u32>, Error>(&bytes).unwrap();
+        let copy: Seq<u32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(copy, sliced);
+        assert!(matches!(copy, Seq::ConsOwn(..)));
+    }
+}
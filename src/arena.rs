@@ -0,0 +1,125 @@
+//! Arena-backed construction of sequences, enabled by feature `arena`.
+//!
+//! A [`SeqArena`] allocates the nodes of sequences in a bump arena, linked by `ConsRef`. The
+//! nodes live as long as the arena, so sequences may be built incrementally in loops and escape
+//! the loop body, without boxing the nodes one by one and without being chained to stack frames.
+//! The memory of all nodes is freed at once when the arena is dropped or reset; the nodes are
+//! not dropped, elements owning resources, such as `String`, are leaked.
+//!
+//! # Example
+//! ```rust
+//! use seq::arena::SeqArena;
+//!
+//! let arena = SeqArena::new();
+//! let mut s = seq::empty();
+//! for i in 0..8u32 {
+//!     s = arena.cons(i, s);
+//! }
+//! assert_eq!(s.len(), 8);
+//! assert_eq!(s.head(), Some(&7));
+//! ```
+//! [`SeqArena`]: struct.SeqArena.html
+
+use bumpalo::Bump;
+
+use super::Seq;
+
+/// An arena allocating the nodes of sequences, freeing them at once
+#[derive(Debug, Default)]
+pub struct SeqArena {
+    bump: Bump,
+}
+
+/// SeqArena method implementations
+impl SeqArena {
+    /// Returns an empty arena
+    pub fn new() -> Self {
+        SeqArena { bump: Bump::new() }
+    }
+
+    /// Returns an empty arena, with room for `bytes` bytes of nodes allocated ahead
+    pub fn with_capacity(bytes: usize) -> Self {
+        SeqArena { bump: Bump::with_capacity(bytes) }
+    }
+
+    /// Allocates the node `seq`, living as long as the arena
+    pub fn alloc<'s, T>(&'s self, seq: Seq<'s, T>) -> &'s Seq<'s, T> {
+        self.bump.alloc(seq)
+    }
+
+    /// Allocates a `ConsRef` node attaching `head` to `tail`, living as long as the arena
+    pub fn cons<'s, T>(&'s self, head: T, tail: &'s Seq<'s, T>) -> &'s Seq<'s, T> {
+        self.alloc(Seq::ConsRef(head, tail))
+    }
+
+    /// Allocates a `ConsSlice` node, a copy of `slice` attached to `tail`, the last element of
+    /// the slice being the head
+    pub fn cons_slice<'s, T: Copy>(&'s self, slice: &[T], tail: &'s Seq<'s, T>)
+        -> &'s Seq<'s, T>
+    {
+        let sl = self.bump.alloc_slice_copy(slice);
+        self.bump.alloc(Seq::ConsSlice(sl, tail))
+    }
+
+    /// Attaches the items of `iter` to `tail` one node per item, the last item being the head
+    pub fn extend<'s, T, I>(&'s self, tail: &'s Seq<'s, T>, iter: I) -> &'s Seq<'s, T>
+        where I: IntoIterator<Item = T>
+    {
+        iter.into_iter().fold(tail, |rt, ft| self.cons(ft, rt))
+    }
+
+    /// Returns the number of bytes allocated by the arena
+    pub fn allocated_bytes(&self) -> usize {
+        self.bump.allocated_bytes()
+    }
+
+    /// Frees the nodes of all sequences at once, keeping the largest chunk of memory for reuse
+    pub fn reset(&mut self) {
+        self.bump.reset();
+    }
+}
+
+/// Wrapping an existing bump arena
+impl From<Bump> for SeqArena {
+    fn from(bump: Bump) -> Self {
+        SeqArena { bump }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{empty, Seq};
+    use super::SeqArena;
+
+    #[test]
+    fn test_cons() {
+        let arena = SeqArena::new();
+        let mut s: &Seq<u32> = empty();
+        for i in 0..100 {
+            s = arena.cons(i, s);
+        }
+        assert_eq!(s.len(), 100);
+        assert!(matches!(*s, Seq::ConsRef(99, _)));
+
+        // sequences of the same arena share their tails
+        let t = arena.cons(7, s.tail().unwrap());
+        assert!(core::ptr::eq(t.tail().unwrap(), s.tail().unwrap()));
+
+        let e = arena.alloc(Seq::Empty);
+        assert!(arena.cons(1, e).tail().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_extend_and_reset() {
+        let mut arena = SeqArena::with_capacity(1024);
+        {
+            let s = arena.extend(empty(), 0..3u8);
+            let t = arena.cons_slice(&[3, 4], s);
+            assert_eq!(*t, [4, 3, 2, 1, 0][..]);
+            assert!(arena.allocated_bytes() > 0);
+        }
+        arena.reset();
+        let s = arena.extend(empty(), "ab".chars());
+        assert_eq!(s.head(), Some(&'b'));
+    }
+}
@@ -0,0 +1,140 @@
+//! Speculative branches on top of a sequence.
+//!
+//! A [`Branch`] records the elements pushed on top of a base sequence in a buffer; owned
+//! `ConsOwn` elements are created only, if the branch is committed. Search algorithms
+//! creating many speculative branches, discarding most of them, save the allocation of the
+//! boxed elements for all discarded branches.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//!
+//! fn extend<'a>(base: &'a Seq<'a, u32>, candidates: &[u32]) -> Option<Seq<'a, u32>> {
+//!    let mut branch = base.branch();
+//!    for c in candidates {
+//!       branch.push(*c);
+//!    }
+//!    branch.commit()
+//! }
+//! ```
+//! [`Branch`]: struct.Branch.html
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::iter::{Chain, Rev};
+use core::slice;
+
+use super::Seq;
+use super::SeqIterator;
+
+/// A branch of pending elements on top of a base sequence
+pub struct Branch<'a, T: 'a> {
+    base: &'a Seq<'a, T>,
+    pending: Vec<T>,
+}
+
+/// The branch method
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns a new branch on top of this sequence
+    pub fn branch(&'a self) -> Branch<'a, T> {
+        Branch { base: self, pending: Vec::new() }
+    }
+}
+
+/// Branch method implementations
+impl<'a, T: 'a> Branch<'a, T> {
+    /// Pushes the element on top of the branch
+    pub fn push(&mut self, value: T) {
+        self.pending.push(value);
+    }
+
+    /// Removes the top most pending element; the elements of the base can not be removed
+    pub fn pop(&mut self) -> Option<T> {
+        self.pending.pop()
+    }
+
+    /// Returns a reference to the head-element
+    pub fn head(&self) -> Option<&T> {
+        match self.pending.last() {
+            Some(ft) => Some(ft),
+            None => self.base.head(),
+        }
+    }
+
+    /// Returns the number of elements, including the base
+    pub fn len(&self) -> usize {
+        self.pending.len() + self.base.len()
+    }
+
+    /// Returns true if the branch and its base are empty
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.base.is_empty()
+    }
+
+    /// Returns the base sequence of the branch
+    pub fn base(&self) -> &'a Seq<'a, T> {
+        self.base
+    }
+
+    /// Returns the pending elements, the top most one last
+    pub fn pending(&self) -> &[T] {
+        &self.pending
+    }
+
+    /// Returns an iterator over the pending elements and the base, starting with the head
+    pub fn iter<'s>(&'s self) -> Chain<Rev<slice::Iter<'s, T>>, SeqIterator<'s, T>> {
+        self.pending.iter().rev().chain(self.base)
+    }
+
+    /// Materializes the pending elements on top of the base; the bottom most pending element
+    /// is referencing the base. Returns `None` if no element is pending, the branch is equal
+    /// to its base.
+    pub fn commit(self) -> Option<Seq<'a, T>> {
+        let mut pending = self.pending.into_iter();
+        let first = pending.next()?;
+        let mut seq = Seq::ConsRef(first, self.base);
+        for ft in pending {
+            seq = Seq::ConsOwn(ft, Box::new(seq));
+        }
+        Some(seq)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Seq;
+    use super::super::empty;
+
+    #[test]
+    fn test_commit() {
+        seqdef!(s; empty() => 0u32, 1);
+        seqdef!(t; &s => 2, 3, 4);
+
+        let mut branch = s.branch();
+        branch.push(2);
+        branch.push(3);
+        branch.push(4);
+        branch.push(5);
+        assert_eq!(branch.pop(), Some(5));
+
+        assert_eq!(branch.len(), 5);
+        assert_eq!(branch.head(), Some(&4));
+        assert_eq!(branch.pending(), &[2, 3, 4]);
+        assert!(branch.iter().eq(t.into_iter()));
+
+        let committed = branch.commit().unwrap();
+        assert_eq!(committed, t);
+    }
+
+    #[test]
+    fn test_commit_empty() {
+        let s0: &Seq<u32> = empty();
+        let s1 = Seq::ConsRef(1u32, s0);
+
+        let branch = s1.branch();
+        assert_eq!(branch.head(), Some(&1));
+        assert!(!branch.is_empty());
+        assert!(branch.commit().is_none());
+        assert!(s0.branch().is_empty());
+    }
+}
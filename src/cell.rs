@@ -0,0 +1,137 @@
+//! Sequences stored together with the storage they borrow from.
+//!
+//! A [`OwnedSeqCell`] owns the backing storage of the elements, such as a `Vec<T>` or an arena,
+//! boxed at a stable address, together with a sequence borrowing from it. The cell is a single
+//! movable value, so a sequence built of `ConsRef` and `ConsSlice` nodes can be stored in a
+//! struct or returned from a function. The storage is never handed out mutably, and the sequence
+//! is dropped before the storage.
+//!
+//! # Example
+//! ```rust
+//! use seq::cell::OwnedSeqCell;
+//!
+//! struct Config {
+//!     path: OwnedSeqCell<Vec<&'static str>, &'static str>,
+//! }
+//!
+//! let config = Config { path: OwnedSeqCell::from_vec(vec!["usr", "local", "bin"]) };
+//! let moved = config;
+//! assert_eq!(moved.path.get().head(), Some(&"bin"));
+//! ```
+//! [`OwnedSeqCell`]: struct.OwnedSeqCell.html
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+use core::ptr::NonNull;
+
+use super::{EmptySeq, Seq};
+
+/// A sequence together with the storage `O` its nodes and elements borrow from
+pub struct OwnedSeqCell<O, T: 'static> {
+    seq: ManuallyDrop<Seq<'static, T>>,
+    // the storage is held by raw pointer, moving the cell does not assert unique access to it
+    owner: NonNull<O>,
+    marker: PhantomData<O>,
+}
+
+/// OwnedSeqCell method implementations
+impl<O, T: 'static> OwnedSeqCell<O, T> {
+    /// Moves `owner` into the cell and builds the sequence borrowing from it by `build`
+    pub fn new<F>(owner: O, build: F) -> Self
+        where F: for<'s> FnOnce(&'s O) -> Seq<'s, T>
+    {
+        let owner = NonNull::from(Box::leak(Box::new(owner)));
+        // the boxed owner keeps its address while the cell is moved, and lives until the
+        // sequence has been dropped; the lifetime is shortened again by `get`
+        let seq = unsafe { mem::transmute::<Seq<'_, T>, Seq<'static, T>>(build(owner.as_ref())) };
+        OwnedSeqCell { seq: ManuallyDrop::new(seq), owner, marker: PhantomData }
+    }
+
+    /// Returns the sequence, borrowing from the cell
+    pub fn get<'s>(&'s self) -> &'s Seq<'s, T> {
+        &self.seq
+    }
+
+    /// Returns the storage the sequence borrows from
+    pub fn owner(&self) -> &O {
+        // the owner is alive until the cell is dropped, and never accessed mutably
+        unsafe { self.owner.as_ref() }
+    }
+
+    /// Drops the sequence and returns the storage
+    pub fn into_owner(self) -> O {
+        let mut cell = ManuallyDrop::new(self);
+        // the sequence borrowing from the owner is dropped first, both are not accessed again
+        unsafe {
+            ManuallyDrop::drop(&mut cell.seq);
+            *Box::from_raw(cell.owner.as_ptr())
+        }
+    }
+}
+
+/// Drops the sequence before the storage it borrows from
+impl<O, T: 'static> Drop for OwnedSeqCell<O, T> {
+    fn drop(&mut self) {
+        // the sequence is not accessed again, the owner has been leaked by `new`
+        unsafe {
+            ManuallyDrop::drop(&mut self.seq);
+            drop(Box::from_raw(self.owner.as_ptr()));
+        }
+    }
+}
+
+// the cell owns the storage, which is shared by the sequence
+unsafe impl<O: Send + Sync, T: 'static> Send for OwnedSeqCell<O, T> where Seq<'static, T>: Send {}
+unsafe impl<O: Sync, T: 'static> Sync for OwnedSeqCell<O, T> where Seq<'static, T>: Sync {}
+
+/// OwnedSeqCell constructor implementations
+impl<T: 'static> OwnedSeqCell<Vec<T>, T> {
+    /// Moves the vector into the cell, linked as a single `ConsSlice` node; the last element of
+    /// the vector becomes the head
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        OwnedSeqCell::new(vec, |v| Seq::ConsSlice(&v[..], EmptySeq::SEQ))
+    }
+}
+
+/// Formats the sequence, hiding the storage
+impl<O, T: fmt::Debug + 'static> fmt::Debug for OwnedSeqCell<O, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("OwnedSeqCell").field(self.get()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Seq;
+    use super::OwnedSeqCell;
+    use alloc::vec::Vec;
+
+    struct Holder {
+        cell: OwnedSeqCell<(Vec<u32>, Seq<'static, u32>), u32>,
+    }
+
+    fn make_holder() -> Holder {
+        let storage = (vec![1, 2, 3], Seq::Empty);
+        let cell = OwnedSeqCell::new(storage, |o| Seq::ConsSlice(&o.0[..], &o.1));
+        Holder { cell }
+    }
+
+    fn assert_send_sync<S: Send + Sync>(_: &S) {}
+
+    #[test]
+    fn test_owned_seq_cell() {
+        let holder = make_holder();
+        let boxed = std::boxed::Box::new(holder);
+        assert_eq!(*boxed.cell.get(), [3, 2, 1][..]);
+        assert_eq!(boxed.cell.owner().0.len(), 3);
+        assert_send_sync(&boxed.cell);
+
+        let cell = OwnedSeqCell::from_vec(vec!["a", "b"]);
+        assert_eq!(cell.get().head(), Some(&"b"));
+        assert!(format!("{:?}", cell).starts_with("OwnedSeqCell(<\"b\""));
+        assert_eq!(cell.into_owner(), ["a", "b"]);
+    }
+}
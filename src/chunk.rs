@@ -0,0 +1,171 @@
+//! Inline chunks of elements, linked into a sequence as single `ConsChunk` node.
+//!
+//! A [`Chunk`] stores up to [`CHUNK_CAPACITY`] elements contiguously within the node, saving
+//! the pointer-chasing per element. Like a slice, the last element pushed is the head.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//! use seq::chunk::Chunk;
+//!
+//! let mut chunk = Chunk::new();
+//! for i in 0..4 {
+//!     assert!(chunk.push(i).is_ok());
+//! }
+//! let s = Seq::ConsChunk(chunk, &Seq::Empty);
+//! assert_eq!(s.head(), Some(&3));
+//! assert_eq!(s.len(), 4);
+//! ```
+//! [`Chunk`]: struct.Chunk.html
+//! [`CHUNK_CAPACITY`]: constant.CHUNK_CAPACITY.html
+
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::slice;
+
+/// The maximum number of elements of a chunk
+pub const CHUNK_CAPACITY: usize = 8;
+
+/// A fixed-capacity, inline array of elements with occupancy count
+pub struct Chunk<T> {
+    items: [MaybeUninit<T>; CHUNK_CAPACITY],
+    len: usize,
+}
+
+/// Chunk method implementations
+impl<T> Chunk<T> {
+    /// Returns an empty chunk
+    pub const fn new() -> Self {
+        Chunk { items: [const { MaybeUninit::uninit() }; CHUNK_CAPACITY], len: 0 }
+    }
+
+    /// Appends the element, handing it back as error if the chunk is full
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+        self.items[self.len] = MaybeUninit::new(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the element appended last, unless empty
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // the element is not accessible any longer, its ownership is moved out
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    /// Returns the number of elements
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the chunk holds no elements
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the chunk holds `CHUNK_CAPACITY` elements
+    pub const fn is_full(&self) -> bool {
+        self.len == CHUNK_CAPACITY
+    }
+
+    /// Returns the elements in the order appended
+    pub const fn as_slice(&self) -> &[T] {
+        // the first `len` items are initialized
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.len) }
+    }
+}
+
+/// By default a chunk is empty
+impl<T> Default for Chunk<T> {
+    fn default() -> Self {
+        Chunk::new()
+    }
+}
+
+/// Dropping a chunk drops the elements it holds
+impl<T> Drop for Chunk<T> {
+    fn drop(&mut self) {
+        let elements = ptr::slice_from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len);
+        self.len = 0;
+        unsafe { ptr::drop_in_place(elements) }
+    }
+}
+
+/// Cloning a chunk clones the elements it holds
+impl<T: Clone> Clone for Chunk<T> {
+    fn clone(&self) -> Self {
+        let mut chunk = Chunk::new();
+        for item in self.as_slice() {
+            let _ = chunk.push(item.clone());
+        }
+        chunk
+    }
+}
+
+/// Debug format of a chunk prints the elements in the order appended
+impl<T: fmt::Debug> fmt::Debug for Chunk<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Chunk, CHUNK_CAPACITY};
+    use super::super::{empty, Seq};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_chunk() {
+        let mut chunk = Chunk::new();
+        assert!(chunk.is_empty());
+        for i in 0..CHUNK_CAPACITY {
+            assert_eq!(chunk.push(i), Ok(()));
+        }
+        assert!(chunk.is_full());
+        assert_eq!(chunk.push(99), Err(99));
+        assert_eq!(chunk.pop(), Some(CHUNK_CAPACITY - 1));
+        assert_eq!(chunk.len(), CHUNK_CAPACITY - 1);
+        assert_eq!(chunk.clone().as_slice(), chunk.as_slice());
+    }
+
+    #[test]
+    fn test_chunk_drop() {
+        let counter = Rc::new(());
+        let mut chunk = Chunk::new();
+        for _ in 0..3 {
+            assert!(chunk.push(counter.clone()).is_ok());
+        }
+        let copy = chunk.clone();
+        assert_eq!(Rc::strong_count(&counter), 7);
+        drop(chunk.pop());
+        drop(chunk);
+        assert_eq!(Rc::strong_count(&counter), 4);
+        drop(copy);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_cons_chunk() {
+        let mut chunk = Chunk::new();
+        for i in 1..4u32 {
+            assert!(chunk.push(i).is_ok());
+        }
+        let s0 = Seq::ConsRef(0u32, empty());
+        let s = Seq::ConsChunk(chunk, &s0);
+
+        assert_eq!(s, [3, 2, 1, 0][..]);
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.tail(), Some(&s0));
+        assert_eq!(s.clone(), s);
+        assert_eq!(s.into_iter().nth(2), Some(&1));
+        assert!(Seq::ConsChunk(Chunk::<u32>::new(), empty()).is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+//! Binary encoding of sequences, without serde, enabled by feature `std`.
+//!
+//! A sequence is encoded as its length, a little-endian `u64`, followed by the elements in
+//! chronological order: the bottom element comes first, the head last. Decoding reads the
+//! elements in the same order, attaching each one as new head of a sequence of boxed
+//! `ConsOwn` elements, so the input is consumed without buffering.
+//!
+//! Elements implementing [`Codec`] are encoded by [`Seq::encode_to`] and decoded by
+//! [`Seq::decode_from`]; the trait is implemented for the primitive numeric types, `bool` and
+//! `char`, all of fixed size and little-endian. For other element types the functions
+//! [`encode_with`] and [`decode_with`] take user-provided element codecs.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//!
+//! let s1 = Seq::ConsRef(1u16, seq::empty());
+//! let s2 = Seq::ConsRef(2u16, &s1);
+//!
+//! let mut bytes = Vec::new();
+//! s2.encode_to(&mut bytes).unwrap();
+//! assert_eq!(bytes, [2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 2, 0]);
+//! ```
+//! [`Codec`]: trait.Codec.html
+//! [`Seq::encode_to`]: ../enum.Seq.html#method.encode_to
+//! [`Seq::decode_from`]: ../enum.Seq.html#method.decode_from
+//! [`encode_with`]: fn.encode_with.html
+//! [`decode_with`]: fn.decode_with.html
+
+#[cfg(not(feature = "lite-seq"))]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use std::io::{self, Read, Write};
+
+use super::Seq;
+
+/// A fixed binary format of elements
+pub trait Codec: Sized {
+    /// Writes the element
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()>;
+
+    /// Reads an element
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! impl_codec_le {
+    ($($t:ty),*) => {
+        $(
+            /// Encoded little-endian, of fixed size
+            impl Codec for $t {
+                fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_all(&self.to_le_bytes())
+                }
+
+                fn decode<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self> {
+                    let mut bytes = [0u8; core::mem::size_of::<$t>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$t>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_le!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// Encoded as single byte, 0 or 1
+impl Codec for bool {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u8).encode(writer)
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self> {
+        match u8::decode(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data("invalid bool")),
+        }
+    }
+}
+
+/// Encoded as scalar value, a little-endian `u32`
+impl Codec for char {
+    fn encode<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u32).encode(writer)
+    }
+
+    fn decode<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self> {
+        char::from_u32(u32::decode(reader)?).ok_or_else(|| invalid_data("invalid char"))
+    }
+}
+
+fn invalid_data(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Writes the length, followed by the elements in chronological order, each one by
+/// `encode`; the element references are buffered
+pub fn encode_with<'a, T, W, F>(seq: &Seq<'a, T>, writer: &mut W, mut encode: F) -> io::Result<()>
+    where W: Write + ?Sized, F: FnMut(&T, &mut W) -> io::Result<()>
+{
+    let buffer: Vec<&T> = seq.into_iter().collect();
+    (buffer.len() as u64).encode(writer)?;
+    for ft in buffer.into_iter().rev() {
+        encode(ft, writer)?;
+    }
+    Ok(())
+}
+
+/// Reads the length, followed by the elements, each one by `decode`; the last element read
+/// becomes the head
+#[cfg(not(feature = "lite-seq"))]
+pub fn decode_with<'a, T, R, F>(reader: &mut R, mut decode: F) -> io::Result<Seq<'a, T>>
+    where T: 'a, R: Read + ?Sized, F: FnMut(&mut R) -> io::Result<T>
+{
+    let len = u64::decode(reader)?;
+    let mut seq = Seq::Empty;
+    for _ in 0..len {
+        seq = Seq::ConsOwn(decode(reader)?, Box::new(seq));
+    }
+    Ok(seq)
+}
+
+/// Seq binary encoding method implementations
+impl<'a, T: Codec + 'a> Seq<'a, T> {
+    /// Writes the length, followed by the elements in chronological order
+    pub fn encode_to<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        encode_with(self, writer, T::encode)
+    }
+
+    /// Reads a sequence written by `encode_to`, the elements being boxed
+    #[cfg(not(feature = "lite-seq"))]
+    pub fn decode_from<R: Read + ?Sized>(reader: &mut R) -> io::Result<Seq<'a, T>> {
+        decode_with(reader, T::decode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::empty;
+    use super::{encode_with, Codec};
+    use std::vec::Vec;
+
+    #[test]
+    fn test_encode() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        let mut out = Vec::new();
+        s.encode_to(&mut out).unwrap();
+        assert_eq!(out.len(), 8 + 3 * 4);
+        assert_eq!(&out[..8], &3u64.to_le_bytes());
+        assert_eq!(&out[8..12], &1u32.to_le_bytes());
+
+        let mut out = Vec::new();
+        empty::<u32>().encode_to(&mut out).unwrap();
+        assert_eq!(out, [0u8; 8]);
+
+        let mut out = Vec::new();
+        encode_with(&s, &mut out, |x, w| (*x as u8).encode(w)).unwrap();
+        assert_eq!(&out[8..], &[1, 2, 3]);
+    }
+
+    #[cfg(not(feature = "lite-seq"))]
+    #[test]
+    fn test_decode() {
+        use super::super::Seq;
+        use super::decode_with;
+        use std::io;
+
+        let data = ['c', 'd'];
+        seqdef!(s; empty() => 'a', 'b');
+        let sliced = Seq::ConsSlice(&data, &s);
+
+        let mut out = Vec::new();
+        sliced.encode_to(&mut out).unwrap();
+        let copy = Seq::<char>::decode_from(&mut &out[..]).unwrap();
+        assert_eq!(copy, sliced);
+        assert!(matches!(copy, Seq::ConsOwn(..)));
+
+        let copy: Seq<u8> = decode_with(&mut &[2, 0, 0, 0, 0, 0, 0, 0, 7, 8][..], |r| {
+            u8::decode(r)
+        }).unwrap();
+        assert_eq!(copy, [8, 7][..]);
+
+        // truncated input and invalid elements are errors
+        let err = Seq::<char>::decode_from(&mut &out[..out.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        let err = Seq::<bool>::decode_from(&mut &[1, 0, 0, 0, 0, 0, 0, 0, 2][..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
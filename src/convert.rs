@@ -0,0 +1,440 @@
+//! Conversions between sequences and standard collections.
+//!
+//! Converting a collection into a sequence, the elements are attached in order, the last one
+//! becoming the head; like the data list of the `seqdef!` macro. Vice versa, [`Seq::to_vec`]
+//! returns the elements starting with the bottom element, so that converting a vector into a
+//! sequence and back yields the original vector. Note, comparing a sequence to a slice or vector
+//! by `PartialEq` follows the iteration order instead, the head comparing to the first element.
+//!
+//! The double-ended queues `VecDeque` and the linked lists `LinkedList` convert in the order of
+//! `Vec`, too. Sequences are converted by reference, cloning the elements, as the nodes may be
+//! borrowed or shared; the collections are converted by value, moving the elements.
+//!
+//! With feature `rpds` enabled, sequences convert to and from the persistent lists `List` and
+//! `ListSync` of crate `rpds`, cloning the elements; the head of the sequence is the first
+//! element of the list.
+//!
+//! With feature `im` enabled, sequences convert to and from the persistent vectors `Vector` of
+//! crate `im`, in the order of `Vec`: the bottom element comes first, the head last.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//!
+//! let data = [0, 1, 2];
+//! let s: Seq<i32> = Seq::from(&data[..]);
+//! assert_eq!(s.head(), Some(&2));
+//!
+//! let t: Seq<i32> = (0..3).collect();
+//! assert_eq!(t, s);
+//!
+//! let u: Seq<i32> = Seq::from(vec![0, 1, 2]);
+//! assert_eq!(u.to_vec(), vec![0, 1, 2]);
+//! ```
+//! [`Seq::to_vec`]: ../enum.Seq.html#method.to_vec
+
+use super::Seq;
+use alloc::boxed::Box;
+use alloc::collections::{LinkedList, VecDeque};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FromIterator;
+use core::mem;
+
+/// The conversion method implementations
+impl<'a, T: Clone + 'a> Seq<'a, T> {
+    /// Returns a vector of cloned elements, starting with the bottom element and ending with the
+    /// head, the reverse of the iteration order compared by `PartialEq<Vec<T>>`. The sequence
+    /// must not be cyclic.
+    pub fn to_vec(&'a self) -> Vec<T> {
+        let mut vec: Vec<T> = self.into_iter().cloned().collect();
+        vec.reverse();
+        vec
+    }
+
+    /// Returns a sequence of boxed `ConsOwn` elements, cloning the elements of the slice, one
+    /// node per element; the last element of the slice becomes the head. Other than
+    /// `Seq::from(slice)`, linking the slice as single `ConsSlice` node, the sequence does not
+    /// borrow the slice.
+    pub fn from_slice(slice: &[T]) -> Seq<'a, T> {
+        slice.iter().cloned().collect()
+    }
+
+    /// Returns a deep copy of boxed `ConsOwn` elements, not borrowing any tail. Other than
+    /// `clone`, which shares the `ConsRef` tails, the copy is independent of the original.
+    ///
+    /// As `Seq` implements `Clone`, it implements `ToOwned` by the blanket implementation of
+    /// the standard library and fits into `Cow`. Thereby the owned type of a `Cow<Seq<'a, T>>`
+    /// is `Seq<'a, T>`, of the same lifetime: `Cow::into_owned` clones the top node only,
+    /// still borrowing the tails. An owned type `Seq<'static, T>` can not be declared instead,
+    /// it would conflict with the blanket implementation, as would `Borrow` between sequences
+    /// of different lifetimes. Borrowed sequences are upgraded to independent ones using
+    /// `to_static`:
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use seq::Seq;
+    ///
+    /// fn upgrade(cow: Cow<Seq<u32>>) -> Seq<'static, u32> {
+    ///     cow.to_static()
+    /// }
+    ///
+    /// let s1 = Seq::ConsRef(1, &Seq::Empty);
+    /// let s2 = Seq::ConsRef(2, &s1);
+    /// let owned: Seq<'static, u32> = upgrade(Cow::Borrowed(&s2));
+    /// assert_eq!(owned, s2);
+    /// ```
+    pub fn to_static(&'a self) -> Seq<'static, T> where T: 'static {
+        let vec: Vec<&T> = self.into_iter().collect();
+        vec.into_iter().rev().cloned().collect()
+    }
+
+    /// Returns a new sequence of boxed `ConsOwn` elements, cloning the elements in sorted order;
+    /// iterating the new sequence, the smallest element comes first, being the head. The sort is
+    /// stable, the sequence must not be cyclic.
+    pub fn to_sorted_owned<'b>(&'a self) -> Seq<'b, T> where T: Ord + 'b {
+        self.to_sorted_owned_by(Ord::cmp)
+    }
+
+    /// Returns a new sorted sequence like [`to_sorted_owned`], comparing by the function
+    ///
+    /// [`to_sorted_owned`]: #method.to_sorted_owned
+    pub fn to_sorted_owned_by<'b, F>(&'a self, mut compare: F) -> Seq<'b, T>
+        where T: 'b, F: FnMut(&T, &T) -> Ordering
+    {
+        let mut vec: Vec<&T> = self.into_iter().collect();
+        vec.sort_by(|a, b| compare(a, b));
+        vec.into_iter().rev().cloned().collect()
+    }
+
+    /// Returns a new sorted sequence like [`to_sorted_owned`], comparing the keys extracted by
+    /// the function
+    ///
+    /// [`to_sorted_owned`]: #method.to_sorted_owned
+    pub fn to_sorted_owned_by_key<'b, K, F>(&'a self, mut f: F) -> Seq<'b, T>
+        where T: 'b, K: Ord, F: FnMut(&T) -> K
+    {
+        self.to_sorted_owned_by(|a, b| f(a).cmp(&f(b)))
+    }
+}
+
+/// Creates a sequence of boxed `ConsOwn` elements, moving the elements out of the vector; the
+/// last element of the vector becomes the head. Thereby `to_vec` returns the original vector,
+/// whereas `PartialEq<Vec<T>>`, comparing in iteration order, equals the reversed vector.
+/// ```rust
+/// use seq::Seq;
+///
+/// let v = vec![0, 1, 2];
+/// let s = Seq::from(v.clone());
+/// assert_eq!(s, [2, 1, 0][..]);
+/// assert_eq!(s.to_vec(), v);
+/// ```
+impl<'a, T: 'a> From<Vec<T>> for Seq<'a, T> {
+    fn from(vec: Vec<T>) -> Seq<'a, T> {
+        vec.into_iter().collect()
+    }
+}
+
+/// Creates a sequence of boxed `ConsOwn` elements, moving the elements out of the array; the
+/// last element of the array becomes the head
+impl<'a, T: 'a, const N: usize> From<[T; N]> for Seq<'a, T> {
+    fn from(array: [T; N]) -> Seq<'a, T> {
+        IntoIterator::into_iter(array).collect()
+    }
+}
+
+/// Collects the items into a sequence of boxed `ConsOwn` elements; the last item yielded
+/// becomes the head
+impl<'a, T: 'a> FromIterator<T> for Seq<'a, T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Seq<'a, T> {
+        let mut seq = Seq::Empty;
+        for ft in iter {
+            seq = Seq::ConsOwn(ft, Box::new(seq));
+        }
+        seq
+    }
+}
+
+/// Attaches the items as boxed `ConsOwn` elements, each item becoming the new head
+impl<'a, T: 'a> Extend<T> for Seq<'a, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for ft in iter {
+            let tail = mem::replace(self, Seq::Empty);
+            *self = Seq::ConsOwn(ft, Box::new(tail));
+        }
+    }
+}
+
+/// Attaches the items as boxed `ConsOwn` elements, each item becoming the new head
+impl<'a, T: 'a> Extend<T> for Box<Seq<'a, T>> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        (**self).extend(iter)
+    }
+}
+
+/// Creates a double-ended queue of cloned elements, starting with the bottom element and ending
+/// with the head, like `to_vec`. The sequence must not be cyclic.
+impl<'a, 'b, T: Clone> From<&'b Seq<'a, T>> for VecDeque<T> {
+    fn from(seq: &'b Seq<'a, T>) -> VecDeque<T> {
+        let mut deque = VecDeque::new();
+        for ft in seq {
+            deque.push_front(ft.clone());
+        }
+        deque
+    }
+}
+
+/// Creates a sequence of boxed `ConsOwn` elements, moving the elements out of the queue; the
+/// back element becomes the head
+impl<'a, T: 'a> From<VecDeque<T>> for Seq<'a, T> {
+    fn from(deque: VecDeque<T>) -> Seq<'a, T> {
+        deque.into_iter().collect()
+    }
+}
+
+/// Creates a linked list of cloned elements, starting with the bottom element and ending with
+/// the head, like `to_vec`. The sequence must not be cyclic.
+impl<'a, 'b, T: Clone> From<&'b Seq<'a, T>> for LinkedList<T> {
+    fn from(seq: &'b Seq<'a, T>) -> LinkedList<T> {
+        let mut list = LinkedList::new();
+        for ft in seq {
+            list.push_front(ft.clone());
+        }
+        list
+    }
+}
+
+/// Creates a sequence of boxed `ConsOwn` elements, moving the elements out of the list; the
+/// back element becomes the head
+impl<'a, T: 'a> From<LinkedList<T>> for Seq<'a, T> {
+    fn from(list: LinkedList<T>) -> Seq<'a, T> {
+        list.into_iter().collect()
+    }
+}
+
+/// Implements the conversions between sequences and the persistent `rpds` lists of either kind
+/// of shared pointer
+#[cfg(feature = "rpds")]
+macro_rules! impl_rpds_list {
+    ($($list:ident),*) => {
+        $(
+            /// Creates a persistent list of cloned elements, the head becoming the first element
+            /// of the list. The sequence must not be cyclic.
+            impl<'a, 'b, T: Clone> From<&'b Seq<'a, T>> for rpds::$list<T> {
+                fn from(seq: &'b Seq<'a, T>) -> rpds::$list<T> {
+                    let buffer: Vec<&T> = seq.into_iter().collect();
+                    let mut list = rpds::$list::default();
+                    for ft in buffer.into_iter().rev() {
+                        list.push_front_mut(ft.clone());
+                    }
+                    list
+                }
+            }
+
+            /// Creates a sequence of boxed `ConsOwn` elements, cloning the elements of the
+            /// persistent list; the first element of the list becomes the head
+            impl<'a, 'b, T: Clone + 'a> From<&'b rpds::$list<T>> for Seq<'a, T> {
+                fn from(list: &'b rpds::$list<T>) -> Seq<'a, T> {
+                    let buffer: Vec<&T> = list.iter().collect();
+                    buffer.into_iter().rev().cloned().collect()
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "rpds")]
+impl_rpds_list!(List, ListSync);
+
+/// Creates a vector of cloned elements, starting with the bottom element and ending with the
+/// head, like `to_vec`. The sequence must not be cyclic.
+#[cfg(feature = "im")]
+impl<'a, 'b, T: Clone> From<&'b Seq<'a, T>> for im::Vector<T> {
+    fn from(seq: &'b Seq<'a, T>) -> im::Vector<T> {
+        let buffer: Vec<&T> = seq.into_iter().collect();
+        buffer.into_iter().rev().cloned().collect()
+    }
+}
+
+/// Creates a sequence of boxed `ConsOwn` elements, moving the elements out of the vector; the
+/// last element of the vector becomes the head
+#[cfg(feature = "im")]
+impl<'a, T: Clone + 'a> From<im::Vector<T>> for Seq<'a, T> {
+    fn from(vector: im::Vector<T>) -> Seq<'a, T> {
+        vector.into_iter().collect()
+    }
+}
+
+/// Creates a sequence of boxed `ConsOwn` elements, cloning the elements of the vector; the
+/// last element of the vector becomes the head
+#[cfg(feature = "im")]
+impl<'a, 'b, T: Clone + 'a> From<&'b im::Vector<T>> for Seq<'a, T> {
+    fn from(vector: &'b im::Vector<T>) -> Seq<'a, T> {
+        vector.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::boxed::Box;
+    use std::string::String;
+    use std::vec::Vec;
+    use super::super::Seq;
+    use super::super::empty;
+
+    #[test]
+    fn test_from_slice() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+
+        let data = [0u32, 1, 2];
+        let t = Seq::from_slice(&data[..]);
+        assert_eq!(t, s);
+        assert_eq!(t.tail().map(Seq::len), Some(2));
+
+        let empty_data: [u32; 0] = [];
+        assert_eq!(&Seq::from_slice(&empty_data[..]), empty());
+    }
+
+    #[test]
+    fn test_from_vec() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+
+        let t = Seq::from(vec![0u32, 1, 2]);
+        assert_eq!(t, s);
+        assert_eq!(t.to_vec(), vec![0, 1, 2]);
+        assert_eq!(s.to_vec(), vec![0, 1, 2]);
+        assert_eq!(empty::<u32>().to_vec(), Vec::<u32>::new());
+
+        let v = vec![0u32, 1, 2];
+        assert_eq!(Seq::from(v.clone()), v.iter().rev().cloned().collect::<Vec<_>>());
+        assert_ne!(Seq::from(v.clone()), v);
+        assert_eq!(Seq::from(v.clone()).to_vec(), v);
+
+        let strings = vec![String::from("a"), String::from("b")];
+        let u = Seq::from(strings.clone());
+        assert_eq!(u.head().map(String::as_str), Some("b"));
+        assert_eq!(u.to_vec(), strings);
+    }
+
+    #[test]
+    fn test_from_array() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+
+        assert_eq!(Seq::from([0u32, 1, 2]), s);
+        assert_eq!(&Seq::from([0u32; 0]), empty());
+        assert!(matches!(Seq::from([String::from("a")]), Seq::ConsOwn(..)));
+    }
+
+    #[test]
+    fn test_extend() {
+        seqdef!(s; empty() => 0u32, 1, 2, 3);
+        seqdef!(t; 0u32, 1);
+
+        let mut u = Seq::ConsRef(2u32, &t);
+        u.extend(Some(3));
+        assert_eq!(u, s);
+
+        let mut v: Box<Seq<u32>> = Box::new(Seq::Empty);
+        v.extend(0..2);
+        v.extend(vec![2, 3]);
+        assert_eq!(*v, s);
+    }
+
+    #[test]
+    fn test_to_static() {
+        use std::borrow::Cow;
+
+        fn keep(cow: Cow<Seq<u32>>) -> Seq<'static, u32> {
+            cow.to_static()
+        }
+
+        let s0 = Seq::ConsRef(0u32, empty());
+        let s1 = Seq::ConsRef(1u32, &s0);
+        let kept = keep(Cow::Borrowed(&s1));
+        assert_eq!(kept, s1);
+        let kept = keep(Cow::Owned(s1.clone()));
+        assert_eq!(kept, [1, 0][..]);
+        assert_eq!(&keep(Cow::Owned(Seq::Empty)), empty());
+
+        // the blanket `ToOwned` of `Clone` keeps the lifetime, sharing the tail
+        let owned: Seq<u32> = Cow::Borrowed(&s1).into_owned();
+        assert!(matches!(owned, Seq::ConsRef(1, rt) if core::ptr::eq(rt, &s0)));
+        assert!(matches!(keep(Cow::Borrowed(&s1)), Seq::ConsOwn(..)));
+    }
+
+    #[test]
+    fn test_to_sorted_owned() {
+        seqdef!(s; empty() => 2u32, 0, 3, 1);
+
+        let sorted = s.to_sorted_owned();
+        assert_eq!(sorted, [0, 1, 2, 3][..]);
+
+        let reversed = s.to_sorted_owned_by(|a, b| b.cmp(a));
+        assert_eq!(reversed, [3, 2, 1, 0][..]);
+
+        seqdef!(t; empty() => ("b", 1u32), ("a", 2), ("c", 1));
+        let by_key = t.to_sorted_owned_by_key(|e| e.1);
+        assert_eq!(by_key, [("c", 1), ("b", 1), ("a", 2)][..]);
+
+        assert_eq!(&empty::<u32>().to_sorted_owned(), empty());
+    }
+
+    #[test]
+    fn test_from_iter() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+
+        let t: Seq<u32> = (0..3).collect();
+        assert_eq!(t, s);
+        assert_eq!(t.head(), Some(&2));
+
+        let u: Seq<u32> = None.into_iter().collect();
+        assert_eq!(&u, empty());
+    }
+
+    #[test]
+    fn test_deque_and_list() {
+        use std::collections::{LinkedList, VecDeque};
+
+        seqdef!(s; empty() => 0u32, 1, 2);
+
+        let deque = VecDeque::from(&s);
+        assert_eq!(deque, [0, 1, 2]);
+        assert_eq!(deque.back(), s.head());
+        assert_eq!(Seq::from(deque), s);
+
+        let list = LinkedList::from(&s);
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(Seq::from(list), s);
+
+        assert!(VecDeque::<u32>::from(empty::<u32>()).is_empty());
+        assert_eq!(&Seq::from(LinkedList::<u32>::new()), empty());
+    }
+
+    #[cfg(feature = "rpds")]
+    #[test]
+    fn test_rpds_list() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+
+        let list: rpds::List<u32> = rpds::List::from(&s);
+        assert_eq!(list.first(), Some(&2));
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec![2, 1, 0]);
+        assert_eq!(Seq::from(&list), s);
+
+        let sync: rpds::ListSync<u32> = rpds::ListSync::from(&s);
+        assert_eq!(Seq::from(&sync.push_front(3)), [3, 2, 1, 0][..]);
+        assert!(rpds::List::<u32>::from(empty::<u32>()).is_empty());
+        assert_eq!(&Seq::from(&rpds::List::<u32>::new()), empty());
+    }
+
+    #[cfg(feature = "im")]
+    #[test]
+    fn test_im_vector() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+
+        let vector = im::Vector::from(&s);
+        assert_eq!(vector, im::vector![0, 1, 2]);
+        assert_eq!(vector.back(), s.head());
+        assert_eq!(Seq::from(&vector), s);
+        assert_eq!(Seq::from(vector), s);
+        assert!(im::Vector::<u32>::from(empty::<u32>()).is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+//! A cursor walking a sequence element by element, with backtracking.
+//!
+//! [`SeqCursor`] moves `down` from the head towards the bottom and back `up` again, recording
+//! the path walked. At any position, [`splice_owned`] rebuilds the sequence with the current
+//! element replaced: the elements above are cloned into boxed `ConsOwn` nodes, the elements
+//! beneath are shared with the original sequence.
+//!
+//! A scan may start cheaply by [`SeqIterator`] and upgrade to editing at the element it is
+//! about to yield: the cursor converted from the iterator is on that element, recording the
+//! path from there on, while [`SeqCursor::iter`] hands the position back to an iterator. The
+//! cursor is the zipper of the sequence, its path being the breadcrumbs walked down; elements
+//! yielded by the iterator before the conversion are not part of the path.
+//!
+//! # Example
+//! ```rust
+//! use seq::cursor::SeqCursor;
+//! use seq::Seq;
+//!
+//! let s1 = Seq::ConsRef(1u32, seq::empty());
+//! let s2 = Seq::ConsRef(2, &s1);
+//!
+//! let mut cursor = SeqCursor::new(&s2);
+//! assert!(cursor.down());
+//! assert_eq!(cursor.current(), Some(&1));
+//! assert!(!cursor.down());
+//! assert!(cursor.up());
+//! assert_eq!(cursor.current(), Some(&2));
+//! ```
+//!
+//! ```rust
+//! use seq::cursor::SeqCursor;
+//! use seq::Seq;
+//!
+//! let data = [0u32, 1, 2, 3, 4];
+//! let s = Seq::ConsSlice(&data, seq::empty());
+//! let mut iter = s.into_iter();
+//! assert_eq!(iter.find(|ft| **ft < 4), Some(&3));
+//!
+//! let mut cursor = SeqCursor::from(iter);
+//! assert_eq!(cursor.current(), Some(&2));
+//! cursor.down();
+//! assert!(cursor.iter().eq([1, 0].iter()));
+//! # #[cfg(not(feature = "lite-seq"))]
+//! assert_eq!(cursor.splice_owned(10), [2, 10, 0][..]);
+//! ```
+//! [`SeqCursor`]: struct.SeqCursor.html
+//! [`splice_owned`]: struct.SeqCursor.html#method.splice_owned
+//! [`SeqIterator`]: ../struct.SeqIterator.html
+//! [`SeqCursor::iter`]: struct.SeqCursor.html#method.iter
+
+#[cfg(not(feature = "lite-seq"))]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use super::{EmptySeq, Seq, SeqIterator};
+
+/// The position of an element, the index into the elements of the node and the node beneath
+#[derive(Clone, Copy)]
+struct Position<'a, T: 'a> {
+    elements: &'a [T],
+    index: usize,
+    tail: &'a Seq<'a, T>,
+}
+
+/// A cursor on an element of a sequence, recording the path from the head
+pub struct SeqCursor<'a, T: 'a> {
+    path: Vec<Position<'a, T>>,
+}
+
+/// SeqCursor method implementations
+impl<'a, T: 'a> SeqCursor<'a, T> {
+    /// Returns a cursor on the head of `seq`; for the empty sequence there is no current element
+    pub fn new(seq: &'a Seq<'a, T>) -> Self {
+        let mut path = Vec::new();
+        if let Some(pos) = first_position(seq) {
+            path.push(pos);
+        }
+        SeqCursor { path }
+    }
+
+    /// Returns the current element, or `None` for the empty sequence
+    pub fn current(&self) -> Option<&'a T> {
+        self.path.last().map(|pos| &pos.elements[pos.index])
+    }
+
+    /// Returns the number of elements above the current one
+    pub fn depth(&self) -> usize {
+        self.path.len().saturating_sub(1)
+    }
+
+    /// Returns the iterator starting with the current element, towards the bottom
+    pub fn iter(&self) -> SeqIterator<'a, T> {
+        match self.path.last() {
+            Some(pos) => SeqIterator { cur: pos.tail, slice: &pos.elements[..=pos.index] },
+            None => SeqIterator { cur: EmptySeq::<'a, T>::SEQ, slice: &[] },
+        }
+    }
+
+    /// Moves to the element beneath, returning false and staying if there is none
+    pub fn down(&mut self) -> bool {
+        let next = match self.path.last() {
+            Some(pos) if pos.index > 0 => Some(Position { index: pos.index - 1, ..*pos }),
+            Some(pos) => first_position(pos.tail),
+            None => None,
+        };
+        match next {
+            Some(pos) => {
+                self.path.push(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves back to the element above, returning false and staying at the head
+    pub fn up(&mut self) -> bool {
+        if self.path.len() > 1 {
+            self.path.pop();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// SeqCursor splicing method implementations
+#[cfg(not(feature = "lite-seq"))]
+impl<'a, T: Clone + 'a> SeqCursor<'a, T> {
+    /// Returns a new sequence, with `value` replacing the current element; the elements above
+    /// are cloned into boxed nodes, the elements beneath are shared. For the empty sequence the
+    /// result holds `value` only.
+    /// ```rust
+    /// use seq::cursor::SeqCursor;
+    /// use seq::Seq;
+    ///
+    /// let s: Seq<u32> = Seq::from(vec![0, 1, 2, 3]);
+    /// let mut cursor = SeqCursor::new(&s);
+    /// cursor.down();
+    /// assert_eq!(cursor.splice_owned(20), [3, 20, 1, 0][..]);
+    /// ```
+    pub fn splice_owned(&self, value: T) -> Seq<'a, T> {
+        let beneath = match self.path.last() {
+            Some(pos) => Seq::ConsSlice(&pos.elements[..pos.index], pos.tail),
+            None => Seq::Empty,
+        };
+        let mut seq = Seq::ConsOwn(value, Box::new(beneath));
+        for pos in self.path.iter().rev().skip(1) {
+            seq = Seq::ConsOwn(pos.elements[pos.index].clone(), Box::new(seq));
+        }
+        seq
+    }
+}
+
+/// The cursor is on the element the iterator would yield next, or has no current element if
+/// the iterator is exhausted
+impl<'a, T: 'a> From<SeqIterator<'a, T>> for SeqCursor<'a, T> {
+    fn from(iter: SeqIterator<'a, T>) -> Self {
+        let pos = match iter.slice.split_last() {
+            Some(_) => Some(Position { elements: iter.slice, index: iter.slice.len() - 1,
+                                       tail: iter.cur }),
+            None => first_position(iter.cur),
+        };
+        SeqCursor { path: pos.into_iter().collect() }
+    }
+}
+
+/// Returns the position of the head of `seq`, skipping nodes without elements
+fn first_position<'a, T: 'a>(seq: &'a Seq<'a, T>) -> Option<Position<'a, T>> {
+    let mut cur = seq;
+    while let Some((sl1, rt1)) = cur.split_node() {
+        if !sl1.is_empty() {
+            return Some(Position { elements: sl1, index: sl1.len() - 1, tail: rt1 });
+        }
+        cur = rt1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{empty, Seq};
+    use super::SeqCursor;
+
+    #[test]
+    fn test_cursor() {
+        let data = [1u32, 2];
+        let s1 = Seq::ConsSlice(&data, empty());
+        let s2 = Seq::ConsSlice(&[], &s1);
+        let s3 = Seq::ConsRef(3, &s2);
+
+        let mut cursor = SeqCursor::new(&s3);
+        assert_eq!(cursor.current(), Some(&3));
+        assert!(!cursor.up());
+        assert!(cursor.down());
+        assert_eq!(cursor.current(), Some(&2));
+        assert!(cursor.down());
+        assert_eq!((cursor.current(), cursor.depth()), (Some(&1), 2));
+        assert!(!cursor.down());
+        assert!(cursor.up());
+        assert_eq!(cursor.current(), Some(&2));
+
+        let empty_cursor = SeqCursor::<u32>::new(empty());
+        assert_eq!(empty_cursor.current(), None);
+    }
+
+    #[cfg(not(feature = "lite-seq"))]
+    #[test]
+    fn test_splice_owned() {
+        let s1 = Seq::ConsRef(1u32, empty());
+        let s2 = Seq::ConsRef(2, &s1);
+        let s3 = Seq::ConsRef(3, &s2);
+
+        let mut cursor = SeqCursor::new(&s3);
+        cursor.down();
+        let edited = cursor.splice_owned(20);
+        assert_eq!(edited, [3, 20, 1][..]);
+        // the element beneath the cursor is shared
+        let beneath = edited.tail().unwrap().tail().unwrap();
+        assert!(core::ptr::eq(beneath.tail().unwrap(), &s1));
+        assert_eq!(SeqCursor::<u32>::new(empty()).splice_owned(7), [7][..]);
+    }
+
+    #[test]
+    fn test_iterator_handoff() {
+        let data = [1u32, 2, 3];
+        let s1 = Seq::ConsSlice(&data, empty());
+        let s2 = Seq::ConsRef(4, &s1);
+
+        let mut iter = s2.into_iter();
+        iter.nth(1);
+        let mut cursor = SeqCursor::from(iter.clone());
+        assert_eq!((cursor.current(), cursor.depth()), (Some(&2), 0));
+        assert!(cursor.iter().eq(iter.clone()));
+        assert!(cursor.down() && !cursor.down());
+        assert!(cursor.iter().eq([1].iter()));
+        assert!(cursor.up() && !cursor.up());
+        assert!(cursor.iter().eq([2, 1].iter()));
+
+        iter.nth(1);
+        let cursor = SeqCursor::from(iter);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.iter().next(), None);
+        assert!(SeqCursor::new(&s2).iter().eq(s2.into_iter()));
+    }
+}
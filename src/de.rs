@@ -0,0 +1,203 @@
+//! Deserialization of sequences with serde, enabled by feature `serde`.
+//!
+//! A list is deserialized into a sequence of boxed `ConsOwn` elements. Matching the
+//! serialization, the first element of the list becomes the head, so sequences round-trip
+//! unchanged. For lists in chronological order, written by
+//! [`serialize_oldest_first`](../ser/fn.serialize_oldest_first.html), the function
+//! [`deserialize_oldest_first`] makes the last element of the list the head.
+//!
+//! With feature `arena` enabled as well, [`ArenaSeed`] allocates the nodes in a caller-provided
+//! bump arena instead, linked by `ConsRef`, and the elements may borrow from the input, such as
+//! `&str`. Deserializing does not box the nodes one by one, and it works with feature
+//! `lite-seq`, too. The arena, [`Bump`] of crate `bumpalo`, frees the memory of the nodes as a
+//! whole, without dropping them; elements owning resources, such as `String`, are leaked.
+//!
+//! [`deserialize_oldest_first`]: fn.deserialize_oldest_first.html
+//! [`ArenaSeed`]: struct.ArenaSeed.html
+//! [`Bump`]: struct.Bump.html
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+#[cfg(feature = "arena")]
+use serde::de::DeserializeSeed;
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+#[cfg(feature = "arena")]
+pub use bumpalo::Bump;
+
+#[cfg(feature = "arena")]
+use super::EmptySeq;
+use super::Seq;
+
+/// The maximum number of elements allocated ahead, trusting the size hint of the input
+const MAX_PREALLOCATED: usize = 4096;
+
+/// Collects the elements of a list in the order read
+struct ListVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ListVisitor<T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<Vec<T>, A::Error> {
+        let hint = access.size_hint().unwrap_or(0);
+        let mut vec = Vec::with_capacity(hint.min(MAX_PREALLOCATED));
+        while let Some(ft) = access.next_element()? {
+            vec.push(ft);
+        }
+        Ok(vec)
+    }
+}
+
+/// A list is deserialized into boxed elements, the first element of the list being the head
+#[cfg(not(feature = "lite-seq"))]
+impl<'de, 'a, T: Deserialize<'de> + 'a> Deserialize<'de> for Seq<'a, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut vec = deserializer.deserialize_seq(ListVisitor(PhantomData))?;
+        vec.reverse();
+        Ok(Seq::from(vec))
+    }
+}
+
+/// Deserializes a list in chronological order, the last element of the list being the head;
+/// to be used as `#[serde(deserialize_with = "seq::de::deserialize_oldest_first")]`
+/// ```rust
+/// use seq::Seq;
+/// use seq::de::deserialize_oldest_first;
+///
+/// let s: Seq<'static, u32> = serde_json::from_str("[3, 2, 1]").unwrap();
+/// assert_eq!(s.head(), Some(&3));
+/// assert_eq!(serde_json::to_string(&s).unwrap(), "[3,2,1]");
+///
+/// let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+/// assert_eq!(deserialize_oldest_first::<u32, _>(&mut de).unwrap(), s);
+/// ```
+#[cfg(not(feature = "lite-seq"))]
+pub fn deserialize_oldest_first<'de, 'a, T, D>(deserializer: D) -> Result<Seq<'a, T>, D::Error>
+    where T: Deserialize<'de> + 'a, D: Deserializer<'de>
+{
+    let vec = deserializer.deserialize_seq(ListVisitor(PhantomData))?;
+    Ok(Seq::from(vec))
+}
+
+/// A deserialization seed, allocating the nodes of the sequence in an arena; the first element
+/// of the list becomes the head
+#[cfg(feature = "arena")]
+pub struct ArenaSeed<'arena, T: 'arena> {
+    arena: &'arena Bump,
+    marker: PhantomData<Seq<'arena, T>>,
+}
+
+/// ArenaSeed method implementations
+#[cfg(feature = "arena")]
+impl<'arena, T: 'arena> ArenaSeed<'arena, T> {
+    /// Returns a seed allocating the nodes in `arena`
+    pub fn new(arena: &'arena Bump) -> Self {
+        ArenaSeed { arena, marker: PhantomData }
+    }
+}
+
+/// The elements are buffered, and linked bottom-up by nodes allocated in the arena
+#[cfg(feature = "arena")]
+impl<'de, 'arena, T: Deserialize<'de> + 'arena> DeserializeSeed<'de> for ArenaSeed<'arena, T> {
+    type Value = &'arena Seq<'arena, T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let vec = deserializer.deserialize_seq(ListVisitor(PhantomData))?;
+        let mut seq: &'arena Seq<'arena, T> = EmptySeq::SEQ;
+        for ft in vec.into_iter().rev() {
+            seq = self.arena.alloc(Seq::ConsRef(ft, seq));
+        }
+        Ok(seq)
+    }
+}
+
+/// Deserializes a list into nodes allocated in `arena`, the first element of the list being the
+/// head; the elements may borrow from the input
+/// ```rust
+/// use seq::de::{deserialize_in, Bump};
+///
+/// let input = String::from(r#"["run", "main"]"#);
+/// let arena = Bump::new();
+/// let mut de = serde_json::Deserializer::from_str(&input);
+/// let s = deserialize_in::<&str, _>(&arena, &mut de).unwrap();
+/// assert_eq!(*s, ["run", "main"][..]);
+/// ```
+#[cfg(feature = "arena")]
+pub fn deserialize_in<'de, 'arena, T, D>(arena: &'arena Bump, deserializer: D)
+    -> Result<&'arena Seq<'arena, T>, D::Error>
+    where T: Deserialize<'de> + 'arena, D: Deserializer<'de>
+{
+    ArenaSeed::new(arena).deserialize(deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Seq;
+    #[cfg(not(feature = "lite-seq"))]
+    use super::deserialize_oldest_first;
+    use std::string::String;
+
+    #[cfg(not(feature = "lite-seq"))]
+    #[test]
+    fn test_deserialize() {
+        let s: Seq<u32> = serde_json::from_str("[3, 2, 1]").unwrap();
+        assert_eq!(s, [3, 2, 1][..]);
+        assert!(matches!(s, Seq::ConsOwn(..)));
+
+        let e: Seq<u32> = serde_json::from_str("[]").unwrap();
+        assert!(e.is_empty());
+        assert!(serde_json::from_str::<Seq<u32>>("[1, \"a\"]").is_err());
+        assert!(serde_json::from_str::<Seq<u32>>("3").is_err());
+    }
+
+    #[cfg(not(feature = "lite-seq"))]
+    #[test]
+    fn test_round_trip() {
+        seqdef!(s; super::super::empty() => String::from("main"), String::from("run"));
+
+        let json = serde_json::to_string(&s).unwrap();
+        let t: Seq<'static, String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(t, s);
+
+        let json = serde_json::to_string(&s.oldest_first()).unwrap();
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let u: Seq<String> = deserialize_oldest_first(&mut de).unwrap();
+        assert_eq!(u, s);
+    }
+
+    #[cfg(not(feature = "lite-seq"))]
+    #[test]
+    fn test_deserialize_long() {
+        let json = format!("[{}0]", "1,".repeat(100_000));
+        let s: Seq<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(s.len(), 100_001);
+        assert_eq!(s.head(), Some(&1));
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn test_deserialize_in() {
+        use super::{deserialize_in, ArenaSeed, Bump};
+        use serde::de::DeserializeSeed;
+
+        let input = String::from(r#"["c", "b", "a"]"#);
+        let arena = Bump::new();
+        let s = deserialize_in::<&str, _>(&arena, &mut serde_json::Deserializer::from_str(&input))
+            .unwrap();
+        assert_eq!(*s, ["c", "b", "a"][..]);
+        assert!(matches!(*s, Seq::ConsRef(..)));
+        // the elements borrow from the input
+        assert!(input.as_ptr() < s.head().unwrap().as_ptr());
+        assert!(arena.allocated_bytes() >= 3 * core::mem::size_of::<Seq<&str>>());
+
+        let numbers = ArenaSeed::<u32>::new(&Bump::new())
+            .deserialize(&mut serde_json::Deserializer::from_str("[]"))
+            .map(|s| s.is_empty());
+        assert_eq!(numbers.ok(), Some(true));
+    }
+}
@@ -0,0 +1,286 @@
+//! The difference of two sequences: the divergent prefixes above their common suffix.
+//!
+//! [`Seq::diff`] aligns both sequences at their bottom and finds the longest common suffix,
+//! of equal elements or of a tail shared by both. Comparing stops as soon as both sides reach
+//! the same shared tail, so two branches of a deep common tail are diffed in time proportional
+//! to the elements above it, plus the walks computing the lengths. Both sequences must not be
+//! cyclic.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//!
+//! let base = Seq::ConsRef(1, &Seq::ConsRef(0, seq::empty()));
+//! let before = Seq::ConsRef(2, &base);
+//! let after = Seq::ConsRef(4, &Seq::ConsRef(3, &base));
+//!
+//! let diff = before.diff(&after);
+//! assert_eq!(diff.left().collect::<Vec<_>>(), [&2]);
+//! assert_eq!(diff.right().collect::<Vec<_>>(), [&4, &3]);
+//! assert_eq!(diff.common_len(), 2);
+//! ```
+//!
+//! The adapter returned by [`SeqDiff::display`] prints the diff line by line, head first, in the
+//! manner of a unified diff: the elements of the first sequence only marked by `-`, those of the
+//! second one only by `+`, followed by the common elements, marked by a space. [`DiffOptions`]
+//! limit the number of common elements printed; with feature `alloc`, [`Seq::render_diff`]
+//! returns the rendered diff as `String`.
+//!
+//! ```rust
+//! use seq::Seq;
+//! use seq::diff::DiffOptions;
+//!
+//! let base = Seq::ConsRef(1, &Seq::ConsRef(0, seq::empty()));
+//! let before = Seq::ConsRef(2, &base);
+//! let after = Seq::ConsRef(3, &base);
+//!
+//! let diff = before.diff(&after);
+//! let opts = DiffOptions::new();
+//! assert_eq!(format!("{}", diff.display(opts)), "-2\n+3\n 1\n 0\n");
+//! assert_eq!(format!("{}", diff.display(opts.context(1))), "-2\n+3\n 1\n ...1 more\n");
+//! ```
+//! [`Seq::diff`]: ../enum.Seq.html#method.diff
+//! [`SeqDiff::display`]: struct.SeqDiff.html#method.display
+//! [`DiffOptions`]: struct.DiffOptions.html
+//! [`Seq::render_diff`]: ../enum.Seq.html#method.render_diff
+
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::iter::Take;
+
+use super::{Seq, SeqIterator};
+
+/// The marker of the elements of the first sequence only
+pub const REMOVED_MARKER: &str = "-";
+
+/// The marker of the elements of the second sequence only
+pub const ADDED_MARKER: &str = "+";
+
+/// The marker of the elements of the common suffix
+pub const KEPT_MARKER: &str = " ";
+
+/// The divergent prefixes of two sequences and the length of their common suffix
+pub struct SeqDiff<'a, T: 'a> {
+    left: &'a Seq<'a, T>,
+    right: &'a Seq<'a, T>,
+    left_len: usize,
+    right_len: usize,
+    common_len: usize,
+}
+
+/// SeqDiff method implementations
+impl<'a, T: 'a> SeqDiff<'a, T> {
+    /// Returns the elements of the first sequence above the common suffix, head first
+    pub fn left(&self) -> Take<SeqIterator<'a, T>> {
+        self.left.into_iter().take(self.left_len)
+    }
+
+    /// Returns the elements of the second sequence above the common suffix, head first
+    pub fn right(&self) -> Take<SeqIterator<'a, T>> {
+        self.right.into_iter().take(self.right_len)
+    }
+
+    /// Returns the elements of the common suffix, as found beneath the first sequence
+    pub fn common(&self) -> SeqIterator<'a, T> {
+        let mut iter = self.left.into_iter();
+        if self.left_len > 0 {
+            iter.nth(self.left_len - 1);
+        }
+        iter
+    }
+
+    /// Returns the number of elements of the common suffix
+    pub fn common_len(&self) -> usize {
+        self.common_len
+    }
+
+    /// Returns true if both sequences are equal
+    pub fn is_equal(&self) -> bool {
+        self.left_len == 0 && self.right_len == 0
+    }
+
+    /// Returns a formatting adapter printing the diff line by line, configured by `opts`
+    pub fn display(&self, opts: DiffOptions) -> DiffDisplay<'_, 'a, T> {
+        DiffDisplay { diff: self, opts }
+    }
+}
+
+/// The options rendering a diff
+#[derive(Clone, Copy, Debug)]
+pub struct DiffOptions {
+    context: usize,
+}
+
+/// DiffOptions method implementations
+impl DiffOptions {
+    /// Returns the options printing all common elements
+    pub const fn new() -> Self {
+        DiffOptions { context: usize::MAX }
+    }
+
+    /// Sets the number of common elements printed at most, the remaining ones being counted by
+    /// the last line
+    pub const fn context(mut self, context: usize) -> Self {
+        self.context = context;
+        self
+    }
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        DiffOptions::new()
+    }
+}
+
+/// A formatting adapter printing a diff line by line, see [`SeqDiff::display`]
+///
+/// [`SeqDiff::display`]: struct.SeqDiff.html#method.display
+pub struct DiffDisplay<'b, 'a: 'b, T: 'a> {
+    diff: &'b SeqDiff<'a, T>,
+    opts: DiffOptions,
+}
+
+/// Each element is printed on a line of its own, prefixed by its marker and using the
+/// formatting options, such as width and precision
+impl<'b, 'a: 'b, T: fmt::Display> fmt::Display for DiffDisplay<'b, 'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let diff = self.diff;
+        let lines = diff.left().map(|ft| (REMOVED_MARKER, ft))
+            .chain(diff.right().map(|ft| (ADDED_MARKER, ft)))
+            .chain(diff.common().take(self.opts.context).map(|ft| (KEPT_MARKER, ft)));
+        for (marker, ft) in lines {
+            f.write_str(marker)?;
+            fmt::Display::fmt(ft, f)?;
+            f.write_str("\n")?;
+        }
+        if diff.common_len > self.opts.context {
+            writeln!(f, "{}...{} more", KEPT_MARKER, diff.common_len - self.opts.context)?;
+        }
+        Ok(())
+    }
+}
+
+/// The diff method
+impl<'a, T: PartialEq> Seq<'a, T> {
+    /// Returns the divergent prefixes of this and the `other` sequence, above the longest common
+    /// suffix of equal elements or shared nodes
+    pub fn diff(&'a self, other: &'a Seq<'a, T>) -> SeqDiff<'a, T> {
+        let (len1, len2) = (self.len(), other.len());
+        let mut iter1 = self.into_iter();
+        let mut iter2 = other.into_iter();
+        // aligning the bottom elements, skipping the excess elements of the longer sequence
+        if len1 > len2 {
+            iter1.nth(len1 - len2 - 1);
+        } else if len2 > len1 {
+            iter2.nth(len2 - len1 - 1);
+        }
+        let aligned = len1.min(len2);
+        // the number of elements compared so far, and where the current run of equal ones began
+        let mut pos = 0;
+        let mut run = 0;
+        while pos < aligned {
+            if iter1.same_position(&iter2) {
+                break;
+            }
+            match (iter1.next(), iter2.next()) {
+                (Some(ft1), Some(ft2)) if ft1 == ft2 => {}
+                _ => run = pos + 1,
+            }
+            pos += 1;
+        }
+        let common_len = aligned - run;
+        SeqDiff {
+            left: self,
+            right: other,
+            left_len: len1 - common_len,
+            right_len: len2 - common_len,
+            common_len,
+        }
+    }
+}
+
+/// The diff rendering method, requiring feature `alloc`
+#[cfg(feature = "alloc")]
+impl<'a, T: PartialEq + fmt::Display> Seq<'a, T> {
+    /// Returns the diff of this and the `other` sequence, rendered line by line as configured by
+    /// `opts`; see [`SeqDiff::display`]
+    ///
+    /// ```rust
+    /// use seq::Seq;
+    /// use seq::diff::DiffOptions;
+    ///
+    /// let base = Seq::ConsRef("main", seq::empty());
+    /// let before = Seq::ConsRef("parse", &base);
+    /// let after = Seq::ConsRef("eval", &base);
+    /// assert_eq!(before.render_diff(&after, DiffOptions::new()), "-parse\n+eval\n main\n");
+    /// ```
+    /// [`SeqDiff::display`]: diff/struct.SeqDiff.html#method.display
+    pub fn render_diff(&'a self, other: &'a Seq<'a, T>, opts: DiffOptions) -> String {
+        self.diff(other).display(opts).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{empty, Seq};
+    use super::DiffOptions;
+
+    #[test]
+    fn test_diff_shared() {
+        let base = Seq::ConsRef(1u32, &Seq::ConsRef(0, empty()));
+        let s1 = Seq::ConsRef(2, &base);
+        let s2 = Seq::ConsRef(4, &Seq::ConsRef(3, &base));
+
+        let diff = s1.diff(&s2);
+        assert!(diff.left().eq([2].iter()));
+        assert!(diff.right().eq([4, 3].iter()));
+        assert!(diff.common().eq([1, 0].iter()));
+        assert!(!diff.is_equal());
+        assert!(base.diff(&base).is_equal());
+    }
+
+    #[test]
+    fn test_diff_values() {
+        let data1 = [0u32, 1, 5, 6];
+        let data2 = [0, 1, 7, 8, 9];
+        let s1 = Seq::ConsSlice(&data1, empty());
+        let s2 = Seq::ConsSlice(&data2, empty());
+
+        let diff = s1.diff(&s2);
+        assert_eq!(diff.common_len(), 2);
+        assert!(diff.left().eq([6, 5].iter()));
+        assert!(diff.right().eq([9, 8, 7].iter()));
+        assert!(diff.common().eq([1, 0].iter()));
+
+        let e: &Seq<u32> = empty();
+        let diff = s1.diff(e);
+        assert_eq!((diff.left().count(), diff.right().count(), diff.common_len()), (4, 0, 0));
+    }
+
+    #[test]
+    fn test_diff_display() {
+        let data1 = [0u32, 1, 5];
+        let data2 = [0, 1, 7, 8];
+        let s1 = Seq::ConsSlice(&data1, empty());
+        let s2 = Seq::ConsSlice(&data2, empty());
+
+        let diff = s1.diff(&s2);
+        let all = DiffOptions::new();
+        assert_eq!(format!("{}", diff.display(all)), "-5\n+8\n+7\n 1\n 0\n");
+        assert_eq!(format!("{}", diff.display(all.context(0))), "-5\n+8\n+7\n ...2 more\n");
+        assert_eq!(format!("{:02}", diff.display(all.context(2))), "-05\n+08\n+07\n 01\n 00\n");
+        assert_eq!(format!("{}", s1.diff(&s1).display(all.context(1))), " 5\n ...2 more\n");
+        let e: &Seq<u32> = empty();
+        assert_eq!(format!("{}", e.diff(e).display(all)), "");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_render_diff() {
+        let base = Seq::ConsRef("main", empty());
+        let s1 = Seq::ConsRef("parse", &base);
+        let s2 = Seq::ConsRef("eval", &base);
+        assert_eq!(s1.render_diff(&s2, DiffOptions::default()), "-parse\n+eval\n main\n");
+    }
+}
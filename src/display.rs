@@ -0,0 +1,231 @@
+//! Formatting adapters for sequences.
+//!
+//! The `Display` format of a sequence prints all elements starting with the head, separated by
+//! [`DEFAULT_SEPARATOR`] and enclosed in brackets, for example `[3, 2, 1]`. The adapter
+//! returned by [`Seq::display`] permits to configure the separator.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//!
+//! let s1 = Seq::ConsRef(1, &Seq::Empty);
+//! let s2 = Seq::ConsRef(2, &s1);
+//!
+//! assert_eq!(format!("{}", s2), "[2, 1]");
+//! assert_eq!(format!("{}", s2.display().separator(" > ")), "[2 > 1]");
+//! ```
+//!
+//! The `Debug` format of a sequence prints the head only, the adapter returned by
+//! [`Seq::debug_n`] prints the first _n_ elements followed by the number of remaining ones.
+//!
+//! ```rust
+//! use seq::Seq;
+//!
+//! let s1 = Seq::ConsRef(1, &Seq::Empty);
+//! let s2 = Seq::ConsRef(2, &s1);
+//! let s3 = Seq::ConsRef(3, &s2);
+//!
+//! assert_eq!(format!("{:?}", s3.debug_n(1)), "[3, ...2 more]");
+//! ```
+//! [`DEFAULT_SEPARATOR`]: constant.DEFAULT_SEPARATOR.html
+//! [`Seq::display`]: ../enum.Seq.html#method.display
+//! [`Seq::debug_n`]: ../enum.Seq.html#method.debug_n
+
+use core::fmt;
+
+use super::{fmt_root_first, Seq};
+
+/// The separator printed between two elements, unless configured otherwise
+pub const DEFAULT_SEPARATOR: &str = ", ";
+
+/// The maximum number of elements printed by the alternate `Debug` format `{:#?}`
+pub const MAX_DEBUG_ELEMENTS: usize = 256;
+
+/// The maximum number of elements logged by the `defmt::Format` implementation
+#[cfg(feature = "defmt")]
+pub const MAX_DEFMT_ELEMENTS: usize = 16;
+
+/// A formatting adapter printing all elements of a sequence
+pub struct SeqDisplay<'a, T: 'a> {
+    seq: &'a Seq<'a, T>,
+    separator: &'a str,
+    oldest_first: bool,
+    brackets: bool,
+}
+
+/// A formatting adapter printing the first elements of a sequence in `Debug` format
+pub struct DebugN<'a, T: 'a> {
+    seq: &'a Seq<'a, T>,
+    n: usize,
+}
+
+/// The formatting adapter methods
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns a formatting adapter printing all elements, starting with the head
+    pub fn display(&'a self) -> SeqDisplay<'a, T> {
+        SeqDisplay {
+            seq: self,
+            separator: DEFAULT_SEPARATOR,
+            oldest_first: false,
+            brackets: true,
+        }
+    }
+
+    /// Returns a formatting adapter printing the first `n` elements in `Debug` format, followed
+    /// by the number of remaining elements. Counting these, the sequence must not be cyclic.
+    pub fn debug_n(&'a self, n: usize) -> DebugN<'a, T> {
+        DebugN { seq: self, n }
+    }
+}
+
+/// SeqDisplay method implementations
+impl<'a, T: 'a> SeqDisplay<'a, T> {
+    /// Sets the separator printed between two elements
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Prints the elements in chronological order, starting with the bottom element and ending
+    /// with the head. With feature `alloc` the element references are buffered; without, each
+    /// one is found walking from the head, in O(n²). The sequence must not be cyclic.
+    ///
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef(1, &Seq::Empty);
+    /// let s2 = Seq::ConsRef(2, &s1);
+    /// assert_eq!(format!("{}", s2.display().oldest_first()), "[1, 2]");
+    /// ```
+    pub fn oldest_first(mut self) -> Self {
+        self.oldest_first = true;
+        self
+    }
+
+    /// Prints the elements without the enclosing brackets
+    ///
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef("config", &Seq::Empty);
+    /// let s2 = Seq::ConsRef("port", &s1);
+    /// assert_eq!(format!("{}", s2.display().oldest_first().separator(".").without_brackets()),
+    ///            "config.port");
+    /// ```
+    pub fn without_brackets(mut self) -> Self {
+        self.brackets = false;
+        self
+    }
+}
+
+/// The elements are printed using the formatting options, such as width and precision
+impl<'a, T: fmt::Display> fmt::Display for SeqDisplay<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.brackets {
+            f.write_str("[")?;
+        }
+        if self.oldest_first {
+            fmt_root_first(self.seq, self.separator, f, |ft, f| fmt::Display::fmt(ft, f))?;
+        } else {
+            for (i, ft) in self.seq.into_iter().enumerate() {
+                if i > 0 {
+                    f.write_str(self.separator)?;
+                }
+                fmt::Display::fmt(ft, f)?;
+            }
+        }
+        if self.brackets {
+            f.write_str("]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Display format of a sequence prints all elements, separated by the default separator
+impl<'a, T: fmt::Display> fmt::Display for Seq<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.display(), f)
+    }
+}
+
+/// The elements are printed as list, the remaining ones being counted by the last entry
+impl<'a, T: fmt::Debug> fmt::Debug for DebugN<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut iter = self.seq.iter_budgeted(self.n);
+        list.entries(iter.by_ref());
+        let remaining = iter.rest_iter().count();
+        if remaining > 0 {
+            list.entry(&format_args!("...{} more", remaining));
+        }
+        list.finish()
+    }
+}
+
+/// The elements are logged as list starting with the head, at most [`MAX_DEFMT_ELEMENTS`];
+/// further elements are marked by `...`, cyclic sequences are logged accordingly
+///
+/// [`MAX_DEFMT_ELEMENTS`]: constant.MAX_DEFMT_ELEMENTS.html
+#[cfg(feature = "defmt")]
+impl<'a, T: defmt::Format> defmt::Format for Seq<'a, T> {
+    fn format(&self, f: defmt::Formatter) {
+        let mut iter = self.iter_budgeted(MAX_DEFMT_ELEMENTS);
+        defmt::write!(f, "[");
+        for (i, ft) in iter.by_ref().enumerate() {
+            if i > 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", ft);
+        }
+        if iter.rest_iter().next().is_some() {
+            defmt::write!(f, ", ...");
+        }
+        defmt::write!(f, "]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::empty;
+
+    #[test]
+    fn test_display() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        assert_eq!(format!("{}", s), "[3, 2, 1]");
+        assert_eq!(format!("{}", empty::<u32>()), "[]");
+        assert_eq!(format!("{}", s.display().separator("/")), "[3/2/1]");
+        assert_eq!(format!("{:02}", s), "[03, 02, 01]");
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format() {
+        fn assert_format<T: defmt::Format>(_: &T) {}
+
+        seqdef!(s; empty() => 1u32, 2, 3);
+        assert_format(&s);
+    }
+
+    #[test]
+    fn test_display_oldest_first() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        assert_eq!(format!("{}", s.display().oldest_first()), "[1, 2, 3]");
+        assert_eq!(format!("{}", s.display().oldest_first().separator(" < ")), "[1 < 2 < 3]");
+        assert_eq!(format!("{}", empty::<u32>().display().oldest_first()), "[]");
+        assert_eq!(format!("{}", s.display().oldest_first().without_brackets()), "1, 2, 3");
+        assert_eq!(format!("{}", empty::<u32>().display().without_brackets()), "");
+    }
+
+    #[test]
+    fn test_debug_n() {
+        seqdef!(s; empty() => 1u32, 2, 3, 4);
+
+        assert_eq!(format!("{:?}", s.debug_n(2)), "[4, 3, ...2 more]");
+        assert_eq!(format!("{:?}", s.debug_n(0)), "[...4 more]");
+        assert_eq!(format!("{:?}", s.debug_n(4)), "[4, 3, 2, 1]");
+        assert_eq!(format!("{:?}", s.debug_n(9)), "[4, 3, 2, 1]");
+        assert_eq!(format!("{:#?}", s.debug_n(1)), "[\n    4,\n    ...3 more,\n]");
+    }
+}
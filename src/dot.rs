@@ -0,0 +1,119 @@
+//! Graphviz export of the sharing structure of sequences.
+//!
+//! [`Seq::to_dot`] and [`to_dot`] emit a directed graph in DOT format, one vertex per node and
+//! an edge from each node to the sequence beneath. Nodes are identified by their address, so a
+//! tail shared by several sequences appears as single vertex, reached by several edges. The
+//! vertices of `ConsSlice` and `ConsChunk` nodes list all elements of the node, starting with
+//! the head; the empty sequences are drawn as points.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//!
+//! let s1 = Seq::ConsRef(1, seq::empty());
+//! let s2 = Seq::ConsRef(2, &s1);
+//! let s3 = Seq::ConsRef(3, &s1);
+//!
+//! let dot = seq::dot::to_dot(&[&s2, &s3]);
+//! assert!(dot.starts_with("digraph seq {"));
+//! assert_eq!(dot.matches("label=\"1\"").count(), 1);
+//! assert_eq!(dot.matches(&format!("-> n{:p};", &s1)).count(), 2);
+//! ```
+//! [`Seq::to_dot`]: ../enum.Seq.html#method.to_dot
+//! [`to_dot`]: fn.to_dot.html
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use super::Seq;
+
+/// Returns the DOT graph of the sequences `roots`, the vertices of shared nodes being emitted
+/// once; the walk terminates for cyclic sequences, too
+pub fn to_dot<'a, T: fmt::Debug>(roots: &[&'a Seq<'a, T>]) -> String {
+    let mut out = String::new();
+    let mut visited = BTreeSet::new();
+    out.push_str("digraph seq {\n");
+    for (i, root) in roots.iter().enumerate() {
+        let _ = writeln!(out, "  root{} [shape=plaintext, label=\"#{}\"];", i, i);
+        let _ = writeln!(out, "  root{} -> n{:p};", i, *root);
+        let mut cur: &'a Seq<'a, T> = root;
+        while visited.insert(cur as *const Seq<'a, T>) {
+            match cur.split_node() {
+                Some((sl1, rt1)) => {
+                    let _ = write!(out, "  n{:p} [shape=box, label=\"", cur);
+                    for (j, ft) in sl1.iter().rev().enumerate() {
+                        if j > 0 {
+                            out.push_str(", ");
+                        }
+                        push_escaped(&mut out, ft);
+                    }
+                    let _ = writeln!(out, "\"];");
+                    let _ = writeln!(out, "  n{:p} -> n{:p};", cur, rt1);
+                    cur = rt1;
+                }
+                None => {
+                    let _ = writeln!(out, "  n{:p} [shape=point];", cur);
+                }
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Appends the `Debug` format of `value`, escaped for a quoted DOT label
+fn push_escaped<T: fmt::Debug>(out: &mut String, value: &T) {
+    let mut label = String::new();
+    let _ = write!(label, "{:?}", value);
+    for ch in label.chars() {
+        if ch == '"' || ch == '\\' {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+}
+
+/// The DOT export method
+impl<'a, T: fmt::Debug> Seq<'a, T> {
+    /// Returns the DOT graph of the sequence, one vertex per node
+    pub fn to_dot(&'a self) -> String {
+        to_dot(&[self])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{empty, Seq};
+    use super::to_dot;
+
+    #[test]
+    fn test_to_dot() {
+        let s1 = Seq::ConsRef("a", empty());
+        let dot = s1.to_dot();
+        assert!(dot.starts_with("digraph seq {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches("label=\"\\\"a\\\"\"").count(), 1);
+        assert_eq!(dot.matches("shape=point").count(), 1);
+        assert_eq!(dot.matches(" -> ").count(), 2);
+
+        let data = [1, 2, 3];
+        let sliced = Seq::ConsSlice(&data, empty());
+        assert!(sliced.to_dot().contains("label=\"3, 2, 1\""));
+    }
+
+    #[test]
+    fn test_to_dot_shared() {
+        let s1 = Seq::ConsRef(1, empty());
+        let s2 = Seq::ConsRef(2, &s1);
+        let s3 = Seq::ConsRef(3, &s1);
+
+        let dot = to_dot(&[&s2, &s3, &s2]);
+        assert_eq!(dot.matches("shape=box").count(), 3);
+        assert_eq!(dot.matches("shape=point").count(), 1);
+        assert_eq!(dot.matches("shape=plaintext").count(), 3);
+        // three root edges and three edges of the nodes to their tails
+        assert_eq!(dot.matches(" -> ").count(), 6);
+        assert_eq!(dot.matches(&format!("-> n{:p};", &s1)).count(), 2);
+    }
+}
@@ -0,0 +1,244 @@
+//! Scoped environments of interpreters, the symbol tables of nested function scopes.
+//!
+//! An [`Env`] is a stack of scopes, each one a slice of bindings borrowed from the stack frame
+//! pushing it, linked as single `ConsSlice` node. Names are looked up walking outward from the
+//! innermost scope; within a scope, the binding listed last wins. Entering a scope does not
+//! copy the bindings of the scopes outside and does not allocate; the scope is popped by
+//! leaving the frame. The environment is an [`AssocSeq`] of the bindings of all scopes. With
+//! feature `alloc`, [`map_keys`] and [`rename`] rebuild the scopes holding affected names, as
+//! needed by alpha-renaming passes, sharing all others.
+//!
+//! # Example
+//! ```rust
+//! use seq::env::Env;
+//!
+//! fn eval(env: &Env<&str, i64>, name: &str) -> i64 {
+//!     *env.lookup(name).expect("unbound name")
+//! }
+//!
+//! let globals = [("x", 1), ("y", 2)];
+//! let root = Env::new();
+//! let env = root.push_scope(&globals);
+//! let locals = [("x", 10)];
+//! let inner = env.push_scope(&locals);
+//! assert_eq!(eval(&inner, "x") + eval(&inner, "y"), 12);
+//! assert_eq!(inner.resolve("y"), Some((1, &2)));
+//! ```
+//! [`Env`]: struct.Env.html
+//! [`AssocSeq`]: ../map/struct.AssocSeq.html
+//! [`map_keys`]: struct.Env.html#method.map_keys
+//! [`rename`]: struct.Env.html#method.rename
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+use super::map::{AssocSeq, Visible};
+use super::Seq;
+
+/// A stack of scopes of bindings, the innermost scope first
+pub struct Env<'a, K: 'a, V: 'a> {
+    map: AssocSeq<'a, K, V>,
+    depth: usize,
+}
+
+/// Env method implementations
+impl<'a, K: 'a, V: 'a> Env<'a, K, V> {
+    /// Returns the environment without any scope
+    pub const fn new() -> Self {
+        Env { map: AssocSeq::new(), depth: 0 }
+    }
+
+    /// Returns a new environment, entering the scope of `bindings` inside this one
+    pub fn push_scope(&'a self, bindings: &'a [(K, V)]) -> Env<'a, K, V> {
+        let seq = Seq::ConsSlice(bindings, self.map.as_seq());
+        Env { map: AssocSeq::from_seq(seq), depth: self.depth + 1 }
+    }
+
+    /// Returns the number of scopes
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the bindings of all scopes, as association list
+    pub fn as_map(&self) -> &AssocSeq<'a, K, V> {
+        &self.map
+    }
+
+    /// Returns the value bound to `name`, walking outward from the innermost scope
+    pub fn lookup<Q>(&'a self, name: &Q) -> Option<&'a V>
+        where K: Borrow<Q>, Q: Eq + ?Sized
+    {
+        self.map.get(name)
+    }
+
+    /// Returns the value bound to `name` and the number of scopes walked outward to find it,
+    /// zero for the innermost scope
+    pub fn resolve<Q>(&'a self, name: &Q) -> Option<(usize, &'a V)>
+        where K: Borrow<Q>, Q: Eq + ?Sized
+    {
+        let mut cur = self.map.as_seq();
+        let mut level = 0;
+        while let Some((sl1, rt1)) = cur.split_node() {
+            if let Some(kv) = sl1.iter().rev().find(|kv| kv.0.borrow() == name) {
+                return Some((level, &kv.1));
+            }
+            level += 1;
+            cur = rt1;
+        }
+        None
+    }
+
+    /// Returns the visible bindings, the innermost first, shadowing resolved
+    pub fn visible(&'a self) -> Visible<'a, K, V> {
+        self.map.iter()
+    }
+
+    /// Returns the iterator over the scopes, the innermost first
+    pub fn scopes(&'a self) -> Scopes<'a, K, V> {
+        Scopes { cur: self.map.as_seq() }
+    }
+}
+
+/// Env key mapping method implementations, requiring feature `alloc`
+#[cfg(feature = "alloc")]
+impl<'a, K: Clone + 'a, V: Clone + 'a> Env<'a, K, V> {
+    /// Calls `f` with the environment of the names mapped by `map`, returning the new name of an
+    /// affected binding or `None` to keep it. The scopes holding affected bindings are cloned
+    /// into vectors, living while `f` runs; the scopes beneath the outermost of them are shared,
+    /// as are the bindings of the unaffected scopes above it.
+    ///
+    /// ```rust
+    /// use seq::env::Env;
+    ///
+    /// let (globals, locals) = ([("tmp", 1), ("y", 2)], [("x", 3)]);
+    /// let root = Env::new();
+    /// let env = root.push_scope(&globals);
+    /// let inner = env.push_scope(&locals);
+    /// let renamed = inner.map_keys(|name| name.strip_prefix("t").map(|_| "t0"), |env| {
+    ///     (env.lookup("t0").copied(), env.lookup("tmp").copied(), env.depth())
+    /// });
+    /// assert_eq!(renamed, (Some(1), None, 2));
+    /// ```
+    pub fn map_keys<M, R, F>(&'a self, mut map: M, f: F) -> R
+        where M: FnMut(&K) -> Option<K>, F: FnOnce(&Env<'_, K, V>) -> R
+    {
+        let scopes: Vec<&[(K, V)]> = self.scopes().collect();
+        let copies: Vec<Option<Vec<(K, V)>>> = scopes.iter().map(|sc| {
+            let names: Vec<Option<K>> = sc.iter().map(|kv| map(&kv.0)).collect();
+            if names.iter().all(Option::is_none) {
+                return None;
+            }
+            Some(sc.iter().zip(names).map(|(kv, name)| {
+                (name.unwrap_or_else(|| kv.0.clone()), kv.1.clone())
+            }).collect())
+        }).collect();
+        let outermost = match copies.iter().rposition(Option::is_some) {
+            Some(i) => i,
+            None => return f(self),
+        };
+        let mut beneath = self.scopes();
+        beneath.nth(outermost);
+        let rebuilt: Vec<&[(K, V)]> = scopes.iter().zip(&copies).take(outermost + 1)
+            .map(|(sc, copy)| copy.as_deref().unwrap_or(sc))
+            .collect();
+        link_scopes(rebuilt[0], &rebuilt[1..], beneath.cur, self.depth, f)
+    }
+
+    /// Calls `f` with the environment of the bindings of `old` renamed to `new`, in all scopes;
+    /// see [`map_keys`]
+    ///
+    /// [`map_keys`]: #method.map_keys
+    pub fn rename<Q, R, F>(&'a self, old: &Q, new: K, f: F) -> R
+        where K: Borrow<Q>, Q: Eq + ?Sized, F: FnOnce(&Env<'_, K, V>) -> R
+    {
+        self.map_keys(|name| if name.borrow() == old { Some(new.clone()) } else { None }, f)
+    }
+}
+
+/// Links the scopes `outer`, the innermost first, onto `beneath` recursively, calling `f` with
+/// the environment of `innermost` on top of them in the innermost frame
+#[cfg(feature = "alloc")]
+fn link_scopes<K, V, R, F>(innermost: &[(K, V)], outer: &[&[(K, V)]], beneath: &Seq<'_, (K, V)>,
+                           depth: usize, f: F) -> R
+    where F: FnOnce(&Env<'_, K, V>) -> R
+{
+    match outer.split_last() {
+        Some((sc, rest)) => link_scopes(innermost, rest, &Seq::ConsSlice(sc, beneath), depth, f),
+        None => f(&Env { map: AssocSeq::from_seq(Seq::ConsSlice(innermost, beneath)), depth }),
+    }
+}
+
+impl<'a, K: 'a, V: 'a> Default for Env<'a, K, V> {
+    fn default() -> Self {
+        Env::new()
+    }
+}
+
+/// The iterator over the scopes of an environment, yielding the bindings of each one
+pub struct Scopes<'a, K: 'a, V: 'a> {
+    cur: &'a Seq<'a, (K, V)>,
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for Scopes<'a, K, V> {
+    type Item = &'a [(K, V)];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (sl1, rt1) = self.cur.split_node()?;
+        self.cur = rt1;
+        Some(sl1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Env;
+
+    #[test]
+    fn test_env() {
+        let outer = [("a", 1u32), ("b", 2)];
+        let inner = [("a", 3), ("c", 4), ("c", 5)];
+        let e0 = Env::new();
+        let e1 = e0.push_scope(&outer);
+        let e2 = e1.push_scope(&inner);
+
+        assert_eq!((e0.depth(), e2.depth()), (0, 2));
+        assert_eq!(e2.lookup("a"), Some(&3));
+        assert_eq!(e2.lookup("c"), Some(&5));
+        assert_eq!(e1.lookup("c"), None);
+        assert_eq!(e2.resolve("b"), Some((1, &2)));
+        assert_eq!(e2.resolve("a"), Some((0, &3)));
+        assert!(e2.visible().eq([("c", 5), ("a", 3), ("b", 2)].iter()));
+        assert!(e2.scopes().map(|sc| sc.len()).eq([3, 2]));
+        assert_eq!(e2.as_map().bindings().count(), 5);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_rename() {
+        let outer = [("a", 1u32), ("b", 2)];
+        let middle = [("c", 3)];
+        let inner = [("a", 4), ("d", 5)];
+        let e0 = Env::new();
+        let e1 = e0.push_scope(&outer);
+        let e2 = e1.push_scope(&middle);
+        let e3 = e2.push_scope(&inner);
+
+        e3.rename("a", "z", |env| {
+            assert_eq!((env.lookup("z"), env.lookup("a")), (Some(&4), None));
+            assert_eq!(env.resolve("b"), Some((2, &2)));
+            assert_eq!(env.depth(), 3);
+            // the bindings of the middle scope are shared
+            assert!(core::ptr::eq(env.scopes().nth(1).unwrap(), &middle[..]));
+        });
+        e3.rename("c", "y", |env| {
+            assert_eq!(env.resolve("y"), Some((1, &3)));
+            // the outer scope is shared, with its node
+            let beneath = env.as_map().as_seq().tail().unwrap().tail().unwrap();
+            assert!(core::ptr::eq(beneath, e1.as_map().as_seq()));
+            assert!(core::ptr::eq(env.scopes().next().unwrap(), &inner[..]));
+        });
+        let depth = e3.rename("x", "w", |env| env.depth());
+        assert_eq!(depth, 3);
+    }
+}
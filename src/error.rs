@@ -0,0 +1,179 @@
+//! Errors carrying the context of the call stack they failed in.
+//!
+//! The frames of a call stack push their context messages onto a sequence as they descend,
+//! each one borrowing the sequence of its caller, without heap allocation. A failing frame
+//! attaches the sequence to its error, in a [`ContextError`] referencing it; if returned
+//! beyond the frames, [`into_owned`] clones the messages into boxed nodes, the only
+//! allocations, on the error path. The `Display` format prints the messages outermost first,
+//! followed by the source error, for example `loading config: line 3: invalid digit`.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//! use seq::error::ResultExt;
+//!
+//! fn parse(ctx: &Seq<&str>, text: &str) -> Result<u32, String> {
+//!     let ctx = Seq::ConsRef("parsing", ctx);
+//!     text.parse::<u32>().in_context(&ctx).map_err(|e| e.to_string())
+//! }
+//!
+//! let ctx = Seq::ConsRef("loading config", seq::empty());
+//! assert_eq!(parse(&ctx, "x").unwrap_err(), "loading config: parsing: invalid digit found in string");
+//! ```
+//! [`ContextError`]: struct.ContextError.html
+//! [`into_owned`]: struct.ContextError.html#method.into_owned
+
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+use alloc::boxed::Box;
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::display::MAX_DEBUG_ELEMENTS;
+use super::{fmt_root_first, Seq, SeqIterator};
+
+/// The separator printed between two context messages and before the source error
+pub const CONTEXT_SEPARATOR: &str = ": ";
+
+/// An error and the context messages of the frames it occurred in, the innermost first
+pub struct ContextError<'a, E, C: 'a = &'static str> {
+    source: E,
+    context: Seq<'a, C>,
+}
+
+/// ContextError method implementations
+impl<'a, E, C: 'a> ContextError<'a, E, C> {
+    /// Returns the error `source` without any context
+    pub const fn new(source: E) -> Self {
+        ContextError { source, context: Seq::Empty }
+    }
+
+    /// Returns the error `source` in the `context` of the frames, referencing the sequence of
+    /// messages
+    pub fn in_context(source: E, context: &'a Seq<'a, C>) -> Self {
+        ContextError { source, context: Seq::ConsSlice(&[], context) }
+    }
+
+    /// Returns the source error
+    pub fn source_ref(&self) -> &E {
+        &self.source
+    }
+
+    /// Returns the source error, discarding the context
+    pub fn into_source(self) -> E {
+        let ContextError { source, .. } = self;
+        source
+    }
+
+    /// Returns the iterator over the context messages, the innermost first
+    pub fn context(&'a self) -> SeqIterator<'a, C> {
+        self.context.into_iter()
+    }
+}
+
+/// ContextError ownership method implementations
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+impl<'a, E, C: Clone + 'a> ContextError<'a, E, C> {
+    /// Returns the error with the context messages cloned into boxed nodes, no longer borrowing
+    /// the frames
+    pub fn into_owned(self) -> ContextError<'static, E, C>
+        where C: 'static
+    {
+        let messages: Vec<C> = self.context.into_iter().cloned().collect();
+        let mut context = Seq::Empty;
+        for msg in messages.into_iter().rev() {
+            context = Seq::ConsOwn(msg, Box::new(context));
+        }
+        ContextError { source: self.into_source(), context }
+    }
+}
+
+/// The context messages are printed outermost first, followed by the source error
+impl<'a, E: fmt::Display, C: fmt::Display> fmt::Display for ContextError<'a, E, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_root_first(&self.context, CONTEXT_SEPARATOR, f, |msg, f| fmt::Display::fmt(msg, f))?;
+        if !self.context.is_empty() {
+            f.write_str(CONTEXT_SEPARATOR)?;
+        }
+        fmt::Display::fmt(&self.source, f)
+    }
+}
+
+/// The source error and the context messages, the innermost first
+impl<'a, E: fmt::Debug, C: fmt::Debug> fmt::Debug for ContextError<'a, E, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ContextError")
+            .field("source", &self.source)
+            .field("context", &self.context.debug_n(MAX_DEBUG_ELEMENTS))
+            .finish()
+    }
+}
+
+/// The source error is returned by `Error::source`
+#[cfg(feature = "std")]
+impl<'a, E, C> std::error::Error for ContextError<'a, E, C>
+    where E: std::error::Error + 'static, C: fmt::Display + fmt::Debug
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Attaching the context of the frames to the error of a result
+pub trait ResultExt<T, E> {
+    /// Returns the error attached to the `context` of the frames, referencing the sequence of
+    /// messages
+    fn in_context<'a, C: 'a>(self, context: &'a Seq<'a, C>) -> Result<T, ContextError<'a, E, C>>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    fn in_context<'a, C: 'a>(self, context: &'a Seq<'a, C>) -> Result<T, ContextError<'a, E, C>> {
+        self.map_err(|e| ContextError::in_context(e, context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContextError, ResultExt};
+    use super::super::{empty, Seq};
+
+    #[test]
+    fn test_in_context() {
+        let c1 = Seq::ConsRef("outer", empty());
+        let c2 = Seq::ConsRef("inner", &c1);
+        let err = ContextError::in_context(7u32, &c2);
+        assert_eq!(format!("{}", err), "outer: inner: 7");
+        assert!(err.context().eq(["inner", "outer"].iter()));
+        assert_eq!(format!("{}", ContextError::<_>::new(7u32)), "7");
+        assert_eq!(*err.source_ref(), 7);
+
+        let res: Result<(), u32> = Err(3);
+        assert_eq!(format!("{}", res.in_context(&c1).unwrap_err()), "outer: 3");
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_into_owned() {
+        fn fail(ctx: &Seq<&'static str>, n: u32) -> ContextError<'static, u32> {
+            if n == 0 {
+                return ContextError::in_context(0, ctx).into_owned();
+            }
+            fail(&Seq::ConsRef("frame", ctx), n - 1)
+        }
+
+        let err = fail(empty(), 2);
+        assert_eq!(format!("{}", err), "frame: frame: 0");
+        assert_eq!(err.into_source(), 0);
+    }
+
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_source() {
+        use std::error::Error;
+
+        let err = "x".parse::<u32>().in_context(empty::<&str>()).unwrap_err();
+        assert!(err.source().is_some());
+        assert!(format!("{:?}", err).starts_with("ContextError"));
+    }
+}
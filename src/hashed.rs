@@ -0,0 +1,148 @@
+//! Sequences caching the hash of each node, enabled by feature `std`.
+//!
+//! A [`HashedSeq`] node stores the hash and the length of itself plus its tail, computed once
+//! when consing. Hashing is O(1), and comparisons fail fast for sequences of distinct hashes or
+//! lengths; identical nodes compare equal without walking, and sequences sharing a tail compare
+//! the elements above the tail only. The sequences suit memoization keys of hash maps.
+//!
+//! # Example
+//! ```rust
+//! use std::collections::HashMap;
+//! use seq::hashed::HashedSeq;
+//!
+//! let base = HashedSeq::empty();
+//! let s1 = HashedSeq::cons(1u32, &base);
+//! let s2 = HashedSeq::cons(2, &s1);
+//!
+//! let mut memo = HashMap::new();
+//! memo.insert(&s2, "cached");
+//! let t1 = HashedSeq::cons(1u32, &base);
+//! let t2 = HashedSeq::cons(2, &t1);
+//! assert_eq!(memo.get(&t2), Some(&"cached"));
+//! assert_eq!(t2.len(), 2);
+//! ```
+//! [`HashedSeq`]: struct.HashedSeq.html
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::ptr;
+use std::collections::hash_map::DefaultHasher;
+
+use super::Seq;
+
+/// A sequence node storing the hash and the length of itself plus its tail
+pub struct HashedSeq<'a, T: 'a> {
+    seq: Seq<'a, T>,
+    tail: Option<&'a HashedSeq<'a, T>>,
+    hash: u64,
+    len: usize,
+}
+
+/// HashedSeq constructor implementations
+impl<'a, T: Hash + 'a> HashedSeq<'a, T> {
+    /// Returns the empty sequence
+    pub fn empty() -> Self {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_usize(0);
+        HashedSeq { seq: Seq::Empty, tail: None, hash: hasher.finish(), len: 0 }
+    }
+
+    /// Returns a new node attaching `head` to `tail`, hashing the head and the hash of the tail
+    pub fn cons(head: T, tail: &'a HashedSeq<'a, T>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(tail.hash);
+        head.hash(&mut hasher);
+        HashedSeq {
+            hash: hasher.finish(),
+            len: tail.len + 1,
+            seq: Seq::ConsRef(head, &tail.seq),
+            tail: Some(tail),
+        }
+    }
+}
+
+/// HashedSeq method implementations
+impl<'a, T: 'a> HashedSeq<'a, T> {
+    /// Returns the sequence
+    pub fn as_seq(&self) -> &Seq<'a, T> {
+        &self.seq
+    }
+
+    /// Returns the tail, unless empty
+    pub fn tail(&self) -> Option<&'a HashedSeq<'a, T>> {
+        self.tail
+    }
+
+    /// Returns the hash cached by the node
+    pub fn hash_value(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the number of elements, cached by the node
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the sequence is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Writes the cached hash only
+impl<'a, T: 'a> Hash for HashedSeq<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/// Identical nodes are equal; distinct hashes or lengths fail fast before comparing elements
+impl<'a, T: PartialEq + 'a> PartialEq for HashedSeq<'a, T> {
+    fn eq(&self, other: &HashedSeq<'a, T>) -> bool {
+        ptr::eq(self, other)
+            || (self.hash == other.hash && self.len == other.len && self.seq == other.seq)
+    }
+}
+
+impl<'a, T: Eq + 'a> Eq for HashedSeq<'a, T> {}
+
+/// Shared access to the sequence
+impl<'a, T: 'a> Deref for HashedSeq<'a, T> {
+    type Target = Seq<'a, T>;
+
+    fn deref(&self) -> &Seq<'a, T> {
+        &self.seq
+    }
+}
+
+/// Formats the sequence
+impl<'a, T: fmt::Debug + 'a> fmt::Debug for HashedSeq<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.seq, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HashedSeq;
+
+    #[test]
+    fn test_hashed_seq() {
+        let e = HashedSeq::empty();
+        let s1 = HashedSeq::cons("a", &e);
+        let s2 = HashedSeq::cons("b", &s1);
+        let t2 = HashedSeq::cons("b", &s1);
+        let u1 = HashedSeq::cons("b", &e);
+
+        assert_eq!(s2, t2);
+        assert_eq!(s2.hash_value(), t2.hash_value());
+        assert_ne!(s2, u1);
+        assert_ne!(s2.hash_value(), s1.hash_value());
+        assert_eq!((s2.len(), e.len()), (2, 0));
+        assert!(e.is_empty());
+        assert!(core::ptr::eq(s2.tail().unwrap(), &s1));
+        assert_eq!(*s2.as_seq(), ["b", "a"][..]);
+        assert_eq!(s2.head(), Some(&"b"));
+    }
+}
@@ -0,0 +1,154 @@
+//! Undo and redo of states, enabled by feature `arc`.
+//!
+//! A [`History`] keeps two sequences of reference-counted `ConsArc` nodes: the past, its head
+//! being the current state, and the future of the states undone. Pushing a state attaches it
+//! to the past and discards the future; undo and redo move the head from one sequence to the
+//! other. A [`snapshot`] of the past clones the head only and shares all states beneath, so
+//! taking snapshots is cheap, and the history keeps working with them held elsewhere.
+//!
+//! # Example
+//! ```rust
+//! use seq::history::History;
+//!
+//! let mut history = History::new("draft");
+//! history.push("edited");
+//! history.push("final");
+//! assert_eq!(history.undo(), Some(&"edited"));
+//! let snapshot = history.snapshot();
+//! assert_eq!(history.redo(), Some(&"final"));
+//! assert_eq!(snapshot, ["edited", "draft"][..]);
+//! ```
+//! [`History`]: struct.History.html
+//! [`snapshot`]: struct.History.html#method.snapshot
+
+use alloc::sync::Arc;
+use core::mem;
+
+use super::{Seq, SeqIterator};
+
+/// The states of a document, the current one plus the past and the future ones
+pub struct History<T: 'static> {
+    // the head is the current state, never empty
+    past: Seq<'static, T>,
+    future: Seq<'static, T>,
+}
+
+/// History method implementations
+impl<T: Clone + 'static> History<T> {
+    /// Returns a history of the `initial` state, nothing to undo or redo
+    pub fn new(initial: T) -> Self {
+        History { past: Seq::ConsArc(initial, Arc::new(Seq::Empty)), future: Seq::Empty }
+    }
+
+    /// Returns the current state
+    pub fn current(&self) -> &T {
+        self.past.head().expect("the past holds the current state")
+    }
+
+    /// Makes `state` the current one, discarding the states undone before
+    pub fn push(&mut self, state: T) {
+        attach(&mut self.past, state);
+        self.future = Seq::Empty;
+    }
+
+    /// Returns true if there is a state before the current one
+    pub fn can_undo(&self) -> bool {
+        self.past.tail().is_some_and(|rt| !rt.is_empty())
+    }
+
+    /// Returns true if there is a state undone before
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+
+    /// Returns to the previous state and returns it, unless the current state is the initial one
+    pub fn undo(&mut self) -> Option<&T> {
+        if !self.can_undo() {
+            return None;
+        }
+        let state = detach(&mut self.past)?;
+        attach(&mut self.future, state);
+        Some(self.current())
+    }
+
+    /// Returns to the state undone last and returns it, if any
+    pub fn redo(&mut self) -> Option<&T> {
+        let state = detach(&mut self.future)?;
+        attach(&mut self.past, state);
+        Some(self.current())
+    }
+
+    /// Returns the past, the current state being the head; cloning the head only, the states
+    /// beneath are shared
+    pub fn snapshot(&self) -> Seq<'static, T> {
+        self.past.clone()
+    }
+
+    /// Returns the iterator over the current state and the past ones, the initial state last
+    pub fn past(&self) -> SeqIterator<'_, T> {
+        self.past.into_iter()
+    }
+
+    /// Returns the iterator over the states undone, the next one to redo first
+    pub fn future(&self) -> SeqIterator<'_, T> {
+        self.future.into_iter()
+    }
+}
+
+/// Attaches `state` as new head of the reference-counted sequence
+fn attach<T>(seq: &mut Seq<'static, T>, state: T) {
+    let rt = mem::replace(seq, Seq::Empty);
+    *seq = Seq::ConsArc(state, Arc::new(rt));
+}
+
+/// Detaches the head of the reference-counted sequence; the tail is moved out of its node,
+/// unless shared by a snapshot
+fn detach<T: Clone>(seq: &mut Seq<'static, T>) -> Option<T> {
+    let (state, rt) = match *seq {
+        Seq::ConsArc(ref ft, ref rt) => (ft.clone(), rt.clone()),
+        _ => return None,
+    };
+    // the node is dropped first, leaving the tail to this function only, if not shared
+    *seq = Seq::Empty;
+    *seq = Arc::try_unwrap(rt).unwrap_or_else(|rt| (*rt).clone());
+    Some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+    use alloc::string::String;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut history = History::new(0u32);
+        assert!(!history.can_undo());
+        assert_eq!(history.undo(), None);
+        history.push(1);
+        history.push(2);
+        assert_eq!(history.undo(), Some(&1));
+        assert_eq!(history.undo(), Some(&0));
+        assert_eq!(history.undo(), None);
+        assert!(history.can_redo());
+        assert_eq!(history.redo(), Some(&1));
+        assert!(history.future().eq([2].iter()));
+
+        // pushing discards the future
+        history.push(3);
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(), None);
+        assert!(history.past().eq([3, 1, 0].iter()));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut history = History::new(String::from("a"));
+        history.push(String::from("b"));
+        let snapshot = history.snapshot();
+        history.undo();
+        history.push(String::from("c"));
+        assert_eq!(snapshot, [String::from("b"), String::from("a")][..]);
+        assert_eq!(history.current(), "c");
+        assert_eq!(history.past().count(), 2);
+    }
+}
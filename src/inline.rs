@@ -0,0 +1,307 @@
+//! Fixed-capacity sequences stored inline, without heap memory.
+//!
+//! An [`InlineSeq`] holds up to `N` elements in an array within the value itself; pushing onto a
+//! full sequence hands the element back. The memory used is bounded at compile time, as
+//! required by embedded environments without allocator.
+//!
+//! As the slice of a `ConsSlice` node, the element pushed last is the head. Borrowing the
+//! elements as [`Seq`] does not copy them; [`with_stack_seq`] builds a sequence of many
+//! elements this way, using a scratch buffer on the stack instead of allocating.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//! use seq::inline::InlineSeq;
+//!
+//! let mut scopes: InlineSeq<&str, 4> = InlineSeq::new();
+//! assert!(scopes.push("main").is_ok());
+//! assert!(scopes.push("run").is_ok());
+//! assert_eq!(scopes.head(), Some(&"run"));
+//!
+//! let s: Seq<&str> = scopes.as_seq();
+//! assert_eq!(s, ["run", "main"][..]);
+//! ```
+//! [`InlineSeq`]: struct.InlineSeq.html
+//! [`Seq`]: ../enum.Seq.html
+//! [`with_stack_seq`]: fn.with_stack_seq.html
+
+use core::fmt;
+use core::iter::Rev;
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::slice;
+
+use super::{EmptySeq, Seq};
+
+/// A sequence of at most `N` elements of type `T`, stored inline
+pub struct InlineSeq<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+/// InlineSeq method implementations
+impl<T, const N: usize> InlineSeq<T, N> {
+    /// The maximum number of elements
+    pub const CAPACITY: usize = N;
+
+    /// Returns an empty sequence
+    pub const fn new() -> Self {
+        InlineSeq { items: [const { MaybeUninit::uninit() }; N], len: 0 }
+    }
+
+    /// Attaches `value` as new head, handing it back as error if the sequence is full
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.items[self.len] = MaybeUninit::new(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the head, the element below becoming the new head
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // the element is not accessible any longer, its ownership is moved out
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    /// Returns a reference to the head-element
+    pub fn head(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    /// Returns the sequence beneath the head, borrowing the elements
+    pub fn tail(&self) -> Option<Seq<'_, T>> {
+        match self.as_slice().split_last() {
+            Some((_, [])) => Some(Seq::Empty),
+            Some((_, rest)) => Some(Seq::ConsSlice(rest, EmptySeq::SEQ)),
+            None => None,
+        }
+    }
+
+    /// Returns the number of elements
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the sequence is empty
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the sequence holds `N` elements
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the elements, the last one being the head
+    pub const fn as_slice(&self) -> &[T] {
+        // the first `len` items are initialized
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.len) }
+    }
+
+    /// Returns the elements as sequence of a single `ConsSlice` node, without copying
+    pub fn as_seq(&self) -> Seq<'_, T> {
+        match self.as_slice() {
+            [] => Seq::Empty,
+            sl => Seq::ConsSlice(sl, EmptySeq::SEQ),
+        }
+    }
+
+    /// Returns an iterator over the elements, starting with the head
+    pub fn iter(&self) -> Rev<slice::Iter<'_, T>> {
+        self.as_slice().iter().rev()
+    }
+
+    /// Attaches the items in order, the last one becoming the head; once the sequence is full,
+    /// the first item not fitting is handed back as error
+    pub fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), T> {
+        for ft in iter {
+            self.push(ft)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects up to `N` items into a scratch buffer on the stack and calls `f` with the
+/// sequence viewing the buffer, the last item being the head; the buffer is dropped on return.
+/// Building the sequence does not allocate. The first item exceeding the capacity is handed
+/// back as error, without calling `f`.
+/// ```rust
+/// use seq::inline::with_stack_seq;
+///
+/// let sum = with_stack_seq::<_, _, _, _, 16>(0..4, |s| s.into_iter().sum::<u32>());
+/// assert_eq!(sum, Ok(6));
+/// assert_eq!(with_stack_seq::<_, _, _, _, 2>(0..4, |s| s.len()), Err(2));
+/// ```
+pub fn with_stack_seq<T, I, R, F, const N: usize>(iter: I, f: F) -> Result<R, T>
+    where I: IntoIterator<Item = T>, F: FnOnce(&Seq<'_, T>) -> R
+{
+    let mut scratch = InlineSeq::<T, N>::new();
+    scratch.try_extend(iter)?;
+    let seq = scratch.as_seq();
+    Ok(f(&seq))
+}
+
+/// InlineSeq conversion method implementations
+impl<T: Clone, const N: usize> InlineSeq<T, N> {
+    /// Returns a copy of the sequence, unless it holds more than `N` elements; the check
+    /// terminates for cyclic sequences, too
+    pub fn try_from_seq(seq: &Seq<'_, T>) -> Option<Self> {
+        if seq.into_iter().nth(N).is_some() {
+            return None;
+        }
+        let mut inline = InlineSeq::new();
+        for ft in seq {
+            let _ = inline.push(ft.clone());
+        }
+        // the elements have been visited head first, the head must be the last one
+        let len = inline.len;
+        unsafe { slice::from_raw_parts_mut(inline.items.as_mut_ptr() as *mut T, len) }.reverse();
+        Some(inline)
+    }
+}
+
+/// By default a sequence is empty
+impl<T, const N: usize> Default for InlineSeq<T, N> {
+    fn default() -> Self {
+        InlineSeq::new()
+    }
+}
+
+/// Dropping a sequence drops the elements it holds
+impl<T, const N: usize> Drop for InlineSeq<T, N> {
+    fn drop(&mut self) {
+        let elements = ptr::slice_from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len);
+        self.len = 0;
+        unsafe { ptr::drop_in_place(elements) }
+    }
+}
+
+/// Cloning a sequence clones the elements it holds
+impl<T: Clone, const N: usize> Clone for InlineSeq<T, N> {
+    fn clone(&self) -> Self {
+        let mut inline = InlineSeq::new();
+        for ft in self.as_slice() {
+            let _ = inline.push(ft.clone());
+        }
+        inline
+    }
+}
+
+/// Sequences are equal, if holding equal elements, irrespective of the capacity
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<InlineSeq<T, M>> for InlineSeq<T, N> {
+    fn eq(&self, other: &InlineSeq<T, M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+/// An inline sequence equals a sequence of the same elements
+impl<'a, T: PartialEq, const N: usize> PartialEq<Seq<'a, T>> for InlineSeq<T, N> {
+    fn eq(&self, other: &Seq<'a, T>) -> bool {
+        self.as_seq() == *other
+    }
+}
+
+/// Debug format of a sequence prints the elements, starting with the head
+impl<T: fmt::Debug, const N: usize> fmt::Debug for InlineSeq<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'b, T, const N: usize> IntoIterator for &'b InlineSeq<T, N> {
+    type Item = &'b T;
+    type IntoIter = Rev<slice::Iter<'b, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Borrowing the elements of an inline sequence as sequence, without copying
+impl<'b, T, const N: usize> From<&'b InlineSeq<T, N>> for Seq<'b, T> {
+    fn from(inline: &'b InlineSeq<T, N>) -> Seq<'b, T> {
+        inline.as_seq()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InlineSeq;
+    use super::super::{empty, Seq};
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_inline_seq() {
+        let mut s: InlineSeq<u32, 3> = InlineSeq::new();
+        assert!(s.is_empty());
+        assert_eq!(s.head(), None);
+        assert_eq!(s.tail(), None);
+
+        assert_eq!(s.push(1), Ok(()));
+        assert_eq!(s.tail(), Some(Seq::Empty));
+        assert_eq!(s.push(2), Ok(()));
+        assert_eq!(s.push(3), Ok(()));
+        assert!(s.is_full());
+        assert_eq!(s.push(4), Err(4));
+
+        assert_eq!(s.head(), Some(&3));
+        assert_eq!(s.tail().unwrap(), [2, 1][..]);
+        assert_eq!(s.iter().cloned().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(format!("{:?}", s), "[3, 2, 1]");
+        assert_eq!(s.clone(), s);
+
+        assert_eq!(s.pop(), Some(3));
+        assert_eq!(s.len(), 2);
+        assert_eq!(InlineSeq::<u32, 3>::CAPACITY, 3);
+    }
+
+    #[test]
+    fn test_inline_seq_convert() {
+        seqdef!(t; empty() => 1u32, 2, 3);
+
+        let s = InlineSeq::<u32, 4>::try_from_seq(&t).unwrap();
+        assert_eq!(s.as_slice(), &[1, 2, 3]);
+        assert_eq!(s, t);
+        assert_eq!(Seq::from(&s), t);
+        assert_eq!(InlineSeq::<u32, 3>::try_from_seq(&t).unwrap(), s);
+        assert_eq!(InlineSeq::<u32, 2>::try_from_seq(&t), None);
+
+        let e = InlineSeq::<u32, 0>::try_from_seq(empty()).unwrap();
+        assert_eq!(e.as_seq(), Seq::Empty);
+    }
+
+    #[test]
+    fn test_try_extend() {
+        use super::with_stack_seq;
+
+        let mut s: InlineSeq<u32, 3> = InlineSeq::new();
+        assert_eq!(s.try_extend(0..2), Ok(()));
+        assert_eq!(s.try_extend(2..5), Err(3));
+        assert_eq!(s.as_seq(), [2, 1, 0][..]);
+
+        let v = with_stack_seq::<_, _, _, _, 3>(vec![1, 2], |s| s.into_iter().cloned().collect());
+        assert_eq!(v, Ok(vec![2u32, 1]));
+        assert_eq!(with_stack_seq::<u32, _, _, _, 3>(None, |s| s.is_empty()), Ok(true));
+    }
+
+    #[test]
+    fn test_inline_seq_drop() {
+        let counter = Rc::new(());
+        {
+            let mut s: InlineSeq<Rc<()>, 4> = InlineSeq::new();
+            for _ in 0..3 {
+                let _ = s.push(counter.clone());
+            }
+            drop(s.pop());
+            assert_eq!(Rc::strong_count(&counter), 3);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,154 @@
+//! Hash-consing of sequence nodes, enabled by the features `arena` and `std`.
+//!
+//! A [`SeqInterner`] allocates `ConsRef` nodes in an arena, and returns the existing node when
+//! consing an element onto a tail the same element has been attached to before. Equal
+//! sequences built independently by the same interner are pointer-identical, so repeated
+//! prefixes, such as paths, are stored once and compare equal in O(1).
+//!
+//! The tails must be nodes of the same interner, starting with its [`empty`] sequence; other
+//! tails make [`cons`] panic. The nodes are freed at once when the interner is dropped, without
+//! being dropped themselves.
+//!
+//! # Example
+//! ```rust
+//! use seq::interner::SeqInterner;
+//!
+//! let interner = SeqInterner::new();
+//! let usr = interner.cons("usr", interner.empty());
+//! let bin = interner.cons("bin", usr);
+//! let other = interner.cons("bin", interner.cons("usr", interner.empty()));
+//! assert!(std::ptr::eq(bin, other));
+//! assert_eq!(interner.len(), 2);
+//! ```
+//! [`SeqInterner`]: struct.SeqInterner.html
+//! [`empty`]: struct.SeqInterner.html#method.empty
+//! [`cons`]: struct.SeqInterner.html#method.cons
+
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+
+use super::arena::SeqArena;
+use super::Seq;
+
+/// An interner of sequence nodes, sharing the nodes of equal elements and tails
+pub struct SeqInterner<T> {
+    arena: SeqArena,
+    empty: *const (),
+    // the candidate nodes by address of the tail and hash of the element
+    nodes: RefCell<HashMap<(usize, u64), Vec<*const ()>>>,
+    owned: RefCell<HashSet<usize>>,
+    marker: PhantomData<T>,
+}
+
+/// SeqInterner method implementations
+impl<T: Hash + Eq> SeqInterner<T> {
+    /// Returns an empty interner
+    pub fn new() -> Self {
+        let arena = SeqArena::new();
+        let empty = arena.alloc(Seq::<T>::Empty) as *const Seq<T> as *const ();
+        let mut owned = HashSet::new();
+        owned.insert(empty as usize);
+        SeqInterner {
+            arena,
+            empty,
+            nodes: RefCell::new(HashMap::new()),
+            owned: RefCell::new(owned),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the empty sequence of this interner, the bottom of all its sequences
+    pub fn empty<'s>(&'s self) -> &'s Seq<'s, T> {
+        // the node lives in the arena as long as the interner
+        unsafe { &*(self.empty as *const Seq<'s, T>) }
+    }
+
+    /// Returns the node attaching `head` to `tail`, the existing one if interned before.
+    ///
+    /// Panics if `tail` is not a node of this interner.
+    pub fn cons<'s>(&'s self, head: T, tail: &'s Seq<'s, T>) -> &'s Seq<'s, T> {
+        let addr = tail as *const Seq<'s, T> as usize;
+        assert!(self.owned.borrow().contains(&addr), "tail is not interned by this interner");
+        let mut hasher = DefaultHasher::new();
+        head.hash(&mut hasher);
+        let key = (addr, hasher.finish());
+
+        let mut nodes = self.nodes.borrow_mut();
+        let candidates = nodes.entry(key).or_default();
+        for &ptr in candidates.iter() {
+            // the candidates are nodes of the arena, living as long as the interner
+            let node = unsafe { &*(ptr as *const Seq<'s, T>) };
+            if let Seq::ConsRef(ref ft, _) = *node {
+                if *ft == head {
+                    return node;
+                }
+            }
+        }
+        let node: &'s Seq<'s, T> = self.arena.cons(head, tail);
+        let ptr = node as *const Seq<'s, T> as *const ();
+        candidates.push(ptr);
+        self.owned.borrow_mut().insert(ptr as usize);
+        node
+    }
+
+    /// Returns the interned copy of `seq`, pointer-identical for equal sequences
+    pub fn intern<'s>(&'s self, seq: &Seq<'_, T>) -> &'s Seq<'s, T>
+        where T: Clone
+    {
+        let elements: Vec<&T> = seq.into_iter().collect();
+        elements.into_iter().rev().fold(self.empty(), |rt, ft| self.cons(ft.clone(), rt))
+    }
+
+    /// Returns the number of nodes interned, the empty sequence not counted
+    pub fn len(&self) -> usize {
+        self.owned.borrow().len() - 1
+    }
+
+    /// Returns true if no node has been interned yet
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Hash + Eq> Default for SeqInterner<T> {
+    fn default() -> Self {
+        SeqInterner::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{empty, Seq};
+    use super::SeqInterner;
+
+    #[test]
+    fn test_interner() {
+        let interner = SeqInterner::new();
+        assert!(interner.is_empty());
+        let a = interner.cons(1u32, interner.empty());
+        let b = interner.cons(2, a);
+        let c = interner.cons(3, a);
+        let b2 = interner.cons(2, interner.cons(1, interner.empty()));
+        assert!(core::ptr::eq(b, b2));
+        assert!(!core::ptr::eq(b, c));
+        assert_eq!(interner.len(), 3);
+
+        let s1 = Seq::ConsRef(1u32, empty());
+        let s2 = Seq::ConsRef(3, &s1);
+        assert!(core::ptr::eq(interner.intern(&s2), c));
+        assert_eq!(*interner.intern(&s2), [3, 1][..]);
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "not interned")]
+    fn test_foreign_tail() {
+        let interner = SeqInterner::new();
+        let s1 = Seq::ConsRef(1u32, empty());
+        interner.cons(2, &s1);
+    }
+}
@@ -0,0 +1,933 @@
+//! Iterator adapters for sequences.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+#[cfg(feature = "unique")]
+use core::hash::Hash;
+use core::iter::{Cloned, Copied};
+#[cfg(feature = "alloc")]
+use core::iter::Rev;
+#[cfg(feature = "unique")]
+use std::collections::HashSet;
+
+use super::{Seq, SeqIterator};
+
+/// The plain iterator methods
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator over references to the elements, starting with the head; same as
+    /// `into_iter` on a reference
+    pub fn iter(&'a self) -> SeqIterator<'a, T> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over copies of the elements, starting with the head
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// fn sum_up(seq: &Seq<u32>) -> u32 {
+    ///    seq.iter_copied().sum()
+    /// }
+    /// ```
+    pub fn iter_copied(&'a self) -> Copied<SeqIterator<'a, T>> where T: Copy {
+        self.into_iter().copied()
+    }
+
+    /// Returns an iterator over clones of the elements, starting with the head
+    pub fn iter_cloned(&'a self) -> Cloned<SeqIterator<'a, T>> where T: Clone {
+        self.into_iter().cloned()
+    }
+}
+
+/// An iterator yielding at most a budget of elements, providing the remaining sequence as
+/// continuation to resume later
+///
+/// # Example
+/// ```rust
+/// use seq::Seq;
+///
+/// fn process_in_slices(seq: &Seq<u32>) -> u32 {
+///    let mut sum = 0;
+///    let mut cur = seq;
+///    while !cur.is_empty() {
+///       let mut iter = cur.iter_budgeted(16);
+///       sum += iter.by_ref().sum::<u32>();
+///       cur = iter.rest();
+///       // interleave other work here
+///    }
+///    sum
+/// }
+/// ```
+/// The continuation [`rest`] is a node of the sequence; having stopped inside a multi-element
+/// node, such as a `ConsSlice` node, it starts beneath that node. The continuation
+/// [`rest_iter`] resumes at the very next element instead:
+/// ```rust
+/// use seq::Seq;
+///
+/// fn process_in_slices(seq: &Seq<u32>) -> u32 {
+///    let mut sum = 0;
+///    let mut rest = seq.into_iter();
+///    loop {
+///       let mut iter = rest.budgeted(16);
+///       sum += iter.by_ref().sum::<u32>();
+///       if iter.budget() > 0 {
+///          return sum; // exhausted the sequence
+///       }
+///       rest = iter.rest_iter();
+///       // interleave other work here
+///    }
+/// }
+/// ```
+/// [`rest`]: #method.rest
+/// [`rest_iter`]: #method.rest_iter
+pub struct BudgetedIter<'a, T: 'a> {
+    iter: SeqIterator<'a, T>,
+    budget: usize,
+}
+
+/// The budgeted iterator method
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator yielding at most `budget` elements, starting with the head
+    pub fn iter_budgeted(&'a self, budget: usize) -> BudgetedIter<'a, T> {
+        self.into_iter().budgeted(budget)
+    }
+}
+
+/// The budgeted continuation method
+impl<'a, T: 'a> SeqIterator<'a, T> {
+    /// Returns an iterator yielding at most `budget` of the remaining elements
+    pub fn budgeted(self, budget: usize) -> BudgetedIter<'a, T> {
+        BudgetedIter { iter: self, budget }
+    }
+}
+
+/// BudgetedIter method implementations
+impl<'a, T: 'a> BudgetedIter<'a, T> {
+    /// Returns the remaining sequence, the elements not yielded yet. Having stopped inside a
+    /// multi-element node, the sequence beneath the node is returned, the elements of the node
+    /// not yielded yet are skipped; see [`rest_iter`].
+    ///
+    /// [`rest_iter`]: #method.rest_iter
+    pub fn rest(&self) -> &'a Seq<'a, T> {
+        self.iter.cur
+    }
+
+    /// Returns an iterator over the remaining elements, the elements not yielded yet, inside
+    /// multi-element nodes, too
+    pub fn rest_iter(&self) -> SeqIterator<'a, T> {
+        self.iter.clone()
+    }
+
+    /// Returns the number of elements that may still be yielded
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+}
+
+/// The budgeted iterator behavior implementation
+impl<'a, T: 'a> Iterator for BudgetedIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.budget == 0 {
+            return None;
+        }
+        let ft = self.iter.next()?;
+        self.budget -= 1;
+        Some(ft)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.budget))
+    }
+}
+
+/// An iterator yielding the starts of the sections of a sequence, see [`Seq::sections`]
+///
+/// [`Seq::sections`]: ../enum.Seq.html#method.sections
+pub struct Sections<'a, T: 'a, P> {
+    next: Option<&'a Seq<'a, T>>,
+    pred: P,
+}
+
+/// An iterator yielding iterators over the sections of a sequence, see [`Seq::section_iters`]
+///
+/// [`Seq::section_iters`]: ../enum.Seq.html#method.section_iters
+pub struct SectionIters<'a, T: 'a, P> {
+    next: Option<SeqIterator<'a, T>>,
+    pred: P,
+}
+
+/// The sections methods
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator splitting the sequence into sections, yielding the start of each
+    /// section. The first section starts at the head, every further section starts at an element
+    /// matching the delimiter predicate `pred`; each section extends up to the start of the
+    /// next one.
+    ///
+    /// A section starts at a node, the predicate is tested on the top element of every node;
+    /// the further elements of a multi-element node, such as a `ConsSlice` node, belong to the
+    /// section of its top element. See [`section_iters`] for sections starting at any element.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// // counts the function boundaries in a sequence of frames, marked by a leading `'f'`
+    /// fn count_functions(frames: &Seq<&str>) -> usize {
+    ///    frames.sections(|frame| frame.starts_with('f')).count()
+    /// }
+    /// ```
+    /// [`section_iters`]: #method.section_iters
+    pub fn sections<P>(&'a self, pred: P) -> Sections<'a, T, P>
+        where P: FnMut(&T) -> bool
+    {
+        let next = if self.is_empty() { None } else { Some(self) };
+        Sections { next, pred }
+    }
+
+    /// Returns an iterator splitting the sequence into sections like [`sections`], yielding an
+    /// iterator starting at each section; sections start at any element matching `pred`, inside
+    /// multi-element nodes, too.
+    ///
+    /// [`sections`]: #method.sections
+    pub fn section_iters<P>(&'a self, pred: P) -> SectionIters<'a, T, P>
+        where P: FnMut(&T) -> bool
+    {
+        let next = if self.is_empty() { None } else { Some(self.into_iter()) };
+        SectionIters { next, pred }
+    }
+}
+
+/// The sections iterator behavior implementation
+impl<'a, T: 'a, P> Iterator for Sections<'a, T, P>
+    where P: FnMut(&T) -> bool
+{
+    type Item = &'a Seq<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.next.take()?;
+        let mut head_seen = false;
+        let mut cur = start;
+        while let Some((sl, rt)) = cur.split_node() {
+            if let Some(ft) = sl.last() {
+                if head_seen && (self.pred)(ft) {
+                    self.next = Some(cur);
+                    break;
+                }
+                head_seen = true;
+            }
+            cur = rt;
+        }
+        Some(start)
+    }
+}
+
+/// The section iterators behavior implementation
+impl<'a, T: 'a, P> Iterator for SectionIters<'a, T, P>
+    where P: FnMut(&T) -> bool
+{
+    type Item = SeqIterator<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.next.take()?;
+        let mut iter = start.clone();
+        iter.next();
+        loop {
+            let pos = iter.clone();
+            match iter.next() {
+                Some(ft) if (self.pred)(ft) => {
+                    self.next = Some(pos);
+                    break;
+                }
+                Some(_) => continue,
+                None => break,
+            }
+        }
+        Some(start)
+    }
+}
+
+/// An iterator yielding the suffix at every node of a sequence, see [`Seq::tails`]
+///
+/// [`Seq::tails`]: ../enum.Seq.html#method.tails
+pub struct Tails<'a, T: 'a> {
+    next: Option<&'a Seq<'a, T>>,
+}
+
+/// The tails method
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator over the suffixes at every node, starting with the sequence itself and
+    /// ending with the empty sequence at the bottom; a `ConsSlice` node is a single suffix. Used
+    /// as scope stack, it walks every enclosing scope.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef(1, &Seq::Empty);
+    /// let s2 = Seq::ConsRef(2, &s1);
+    ///
+    /// let lens: Vec<usize> = s2.tails().map(|t| t.len()).collect();
+    /// assert_eq!(lens, vec![2, 1, 0]);
+    /// ```
+    pub fn tails(&'a self) -> Tails<'a, T> {
+        Tails { next: Some(self) }
+    }
+}
+
+/// The tails iterator behavior implementation
+impl<'a, T: 'a> Iterator for Tails<'a, T> {
+    type Item = &'a Seq<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur = self.next?;
+        self.next = cur.tail();
+        Some(cur)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.next {
+            Some(_) => (1, None),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+/// An iterator yielding the elements together with their index, see [`Seq::enumerate_depth`]
+/// and [`Seq::enumerate_height`]
+///
+/// [`Seq::enumerate_depth`]: ../enum.Seq.html#method.enumerate_depth
+/// [`Seq::enumerate_height`]: ../enum.Seq.html#method.enumerate_height
+pub struct Enumerate<'a, T: 'a> {
+    iter: SeqIterator<'a, T>,
+    index: usize,
+    from_bottom: bool,
+}
+
+/// The enumerating iterator methods
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator yielding `(depth, element)`, the depth being the distance from the
+    /// head; the head has depth 0
+    pub fn enumerate_depth(&'a self) -> Enumerate<'a, T> {
+        Enumerate { iter: self.into_iter(), index: 0, from_bottom: false }
+    }
+
+    /// Returns an iterator yielding `(height, element)`, the height being the distance from the
+    /// bottom element; the bottom element has height 0, numbering the elements chronologically.
+    /// The length is computed up front, the sequence must not be cyclic.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef("first", &Seq::Empty);
+    /// let s2 = Seq::ConsRef("second", &s1);
+    ///
+    /// let numbered: Vec<(usize, &&str)> = s2.enumerate_height().collect();
+    /// assert_eq!(numbered, vec![(1, &"second"), (0, &"first")]);
+    /// ```
+    pub fn enumerate_height(&'a self) -> Enumerate<'a, T> {
+        Enumerate { iter: self.into_iter(), index: self.len(), from_bottom: true }
+    }
+}
+
+/// The enumerating iterator behavior implementation
+impl<'a, T: 'a> Iterator for Enumerate<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ft = self.iter.next()?;
+        let index = if self.from_bottom {
+            self.index -= 1;
+            self.index
+        } else {
+            self.index += 1;
+            self.index - 1
+        };
+        Some((index, ft))
+    }
+}
+
+/// An iterator yielding the pairs of adjacent elements, see [`Seq::pairs`]
+///
+/// [`Seq::pairs`]: ../enum.Seq.html#method.pairs
+pub struct Pairs<'a, T: 'a> {
+    iter: SeqIterator<'a, T>,
+    prev: Option<&'a T>,
+}
+
+/// An iterator yielding the overlapping windows of `N` adjacent elements, see [`Seq::windows`]
+///
+/// [`Seq::windows`]: ../enum.Seq.html#method.windows
+pub struct Windows<'a, T: 'a, const N: usize> {
+    iter: SeqIterator<'a, T>,
+}
+
+/// The adjacent elements iterator methods
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator yielding each element paired with its successor towards the bottom,
+    /// starting with the head
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// fn has_repeated_scope(scopes: &Seq<&str>) -> bool {
+    ///    scopes.pairs().any(|(a, b)| a == b)
+    /// }
+    /// ```
+    pub fn pairs(&'a self) -> Pairs<'a, T> {
+        Pairs { iter: self.into_iter(), prev: None }
+    }
+
+    /// Returns an iterator yielding the windows of `N` adjacent elements, starting with the
+    /// window at the head; `N` must not be zero
+    pub fn windows<const N: usize>(&'a self) -> Windows<'a, T, N> {
+        const { assert!(N > 0, "the window size must not be zero") };
+        Windows { iter: self.into_iter() }
+    }
+}
+
+/// The pairs iterator behavior implementation
+impl<'a, T: 'a> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ft = match self.prev {
+            Some(prev) => prev,
+            None => self.iter.next()?,
+        };
+        let next = self.iter.next()?;
+        self.prev = Some(next);
+        Some((ft, next))
+    }
+}
+
+/// The windows iterator behavior implementation
+impl<'a, T: 'a, const N: usize> Iterator for Windows<'a, T, N> {
+    type Item = [&'a T; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut window = [None; N];
+        let mut probe = self.iter.clone();
+        for slot in window.iter_mut() {
+            *slot = Some(probe.next()?);
+        }
+        self.iter.next();
+        Some(window.map(|ft| ft.unwrap()))
+    }
+}
+
+/// An iterator yielding the elements with a separator between each two, see
+/// [`Seq::intersperse`]
+///
+/// [`Seq::intersperse`]: ../enum.Seq.html#method.intersperse
+pub struct Intersperse<'a, T: 'a> {
+    iter: SeqIterator<'a, T>,
+    sep: &'a T,
+    sep_next: bool,
+}
+
+/// The intersperse method
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator yielding the elements starting with the head, yielding the separator
+    /// `sep` between each two adjacent elements. Owned items are produced by `cloned()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef("usr", &Seq::Empty);
+    /// let s2 = Seq::ConsRef("bin", &s1);
+    ///
+    /// let path: String = s2.intersperse(&"/").cloned().collect();
+    /// assert_eq!(path, "bin/usr");
+    /// ```
+    pub fn intersperse(&'a self, sep: &'a T) -> Intersperse<'a, T> {
+        Intersperse { iter: self.into_iter(), sep, sep_next: false }
+    }
+}
+
+/// The intersperse iterator behavior implementation
+impl<'a, T: 'a> Iterator for Intersperse<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sep_next && self.iter.clone().next().is_some() {
+            self.sep_next = false;
+            return Some(self.sep);
+        }
+        let ft = self.iter.next()?;
+        self.sep_next = true;
+        Some(ft)
+    }
+}
+
+/// An iterator skipping the elements equal to their predecessor, see [`Seq::dedup_by`]
+///
+/// [`Seq::dedup_by`]: ../enum.Seq.html#method.dedup_by
+pub struct DedupBy<'a, T: 'a, F> {
+    iter: SeqIterator<'a, T>,
+    prev: Option<&'a T>,
+    same: F,
+}
+
+/// An iterator skipping the elements equal to their predecessor, see [`Seq::dedup`]
+///
+/// [`Seq::dedup`]: ../enum.Seq.html#method.dedup
+pub type Dedup<'a, T> = DedupBy<'a, T, fn(&T, &T) -> bool>;
+
+/// The consecutive-duplicate removal methods
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator yielding the elements starting with the head, skipping each element
+    /// equal to the element yielded last; runs of equal elements are yielded once.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef("open", &Seq::Empty);
+    /// let s2 = Seq::ConsRef("read", &s1);
+    /// let s3 = Seq::ConsRef("read", &s2);
+    ///
+    /// assert_eq!(s3.dedup().collect::<Vec<_>>(), vec![&"read", &"open"]);
+    /// ```
+    pub fn dedup(&'a self) -> Dedup<'a, T> where T: PartialEq {
+        self.dedup_by(PartialEq::eq)
+    }
+
+    /// Returns an iterator like [`dedup`], the function `same` deciding whether the element
+    /// (second argument) duplicates the element yielded last (first argument)
+    ///
+    /// [`dedup`]: #method.dedup
+    pub fn dedup_by<F>(&'a self, same: F) -> DedupBy<'a, T, F>
+        where F: FnMut(&T, &T) -> bool
+    {
+        DedupBy { iter: self.into_iter(), prev: None, same }
+    }
+}
+
+/// The consecutive-duplicate removal iterator behavior implementation
+impl<'a, T: 'a, F> Iterator for DedupBy<'a, T, F>
+    where F: FnMut(&T, &T) -> bool
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for ft in self.iter.by_ref() {
+            match self.prev {
+                Some(prev) if (self.same)(prev, ft) => continue,
+                _ => {
+                    self.prev = Some(ft);
+                    return Some(ft);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// An iterator yielding each distinct element once, see [`Seq::unique`]
+///
+/// [`Seq::unique`]: ../enum.Seq.html#method.unique
+#[cfg(feature = "unique")]
+pub struct Unique<'a, T: 'a> {
+    iter: SeqIterator<'a, T>,
+    seen: HashSet<&'a T>,
+}
+
+/// The unique method, requiring feature `unique`
+#[cfg(feature = "unique")]
+impl<'a, T: Hash + Eq + 'a> Seq<'a, T> {
+    /// Returns an iterator yielding the elements starting with the head, skipping each element
+    /// yielded before. The yielded elements are remembered in a hash set.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef("main", &Seq::Empty);
+    /// let s2 = Seq::ConsRef("run", &s1);
+    /// let s3 = Seq::ConsRef("main", &s2);
+    ///
+    /// assert_eq!(s3.unique().collect::<Vec<_>>(), vec![&"main", &"run"]);
+    /// ```
+    pub fn unique(&'a self) -> Unique<'a, T> {
+        Unique { iter: self.into_iter(), seen: HashSet::new() }
+    }
+}
+
+/// The unique iterator behavior implementation
+#[cfg(feature = "unique")]
+impl<'a, T: Hash + Eq + 'a> Iterator for Unique<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let seen = &mut self.seen;
+        self.iter.by_ref().find(|ft| seen.insert(*ft))
+    }
+}
+
+/// An iterator knowing the number of remaining elements, see [`Seq::iter_with_len`]
+///
+/// [`Seq::iter_with_len`]: ../enum.Seq.html#method.iter_with_len
+pub struct ExactIter<'a, T: 'a> {
+    iter: SeqIterator<'a, T>,
+    len: usize,
+}
+
+/// The exact size iterator methods
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns an iterator starting with the head, implementing `ExactSizeIterator`. The length
+    /// is computed up front, the sequence must not be cyclic.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef(1, &Seq::Empty);
+    /// let s2 = Seq::ConsRef(2, &s1);
+    ///
+    /// assert_eq!(s2.iter_with_len().len(), 2);
+    /// ```
+    pub fn iter_with_len(&'a self) -> ExactIter<'a, T> {
+        self.iter_with_known_len(self.len())
+    }
+
+    /// Returns an iterator like [`iter_with_len`], using the length `len` known by the caller.
+    /// The length must be equal to the one of the sequence, this is checked in debug builds.
+    /// Otherwise, in release builds, all elements of the sequence are yielded nevertheless, the
+    /// length reported by `len` and `size_hint` being wrong: counting down with every element,
+    /// it stays at 0 once reached, too small, or remains above 0 at the end, too large.
+    ///
+    /// [`iter_with_len`]: #method.iter_with_len
+    pub fn iter_with_known_len(&'a self, len: usize) -> ExactIter<'a, T> {
+        debug_assert_eq!(len, self.len(), "known length differs from sequence length");
+        ExactIter { iter: self.into_iter(), len }
+    }
+}
+
+/// The exact size iterator behavior implementation
+impl<'a, T: 'a> Iterator for ExactIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ft = self.iter.next()?;
+        debug_assert!(self.len > 0, "known length smaller than sequence length");
+        self.len = self.len.saturating_sub(1);
+        Some(ft)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+/// The number of remaining elements is known
+impl<'a, T: 'a> ExactSizeIterator for ExactIter<'a, T> {}
+
+/// A double-ended iterator over a snapshot of the element references, see
+/// [`Seq::iter_buffered`]
+///
+/// [`Seq::iter_buffered`]: ../enum.Seq.html#method.iter_buffered
+#[cfg(feature = "alloc")]
+pub struct BufferedIter<'a, T: 'a> {
+    buffer: vec::IntoIter<&'a T>,
+}
+
+/// The buffered iterator method, requiring feature `alloc`
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns a double-ended iterator, starting with the head. On construction the sequence is
+    /// walked once, buffering the element references in a vector; the sequence must not be
+    /// cyclic.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef(1, &Seq::Empty);
+    /// let s2 = Seq::ConsRef(2, &s1);
+    ///
+    /// assert_eq!(s2.iter_buffered().rev().collect::<Vec<_>>(), vec![&1, &2]);
+    /// ```
+    pub fn iter_buffered(&'a self) -> BufferedIter<'a, T> {
+        let buffer: Vec<&'a T> = self.into_iter().collect();
+        BufferedIter { buffer: buffer.into_iter() }
+    }
+
+    /// Returns an iterator yielding the elements oldest first, starting with the bottom element
+    /// and ending with the head. The sequence is walked once, buffering the element references.
+    ///
+    /// # Example
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef("login", &Seq::Empty);
+    /// let s2 = Seq::ConsRef("logout", &s1);
+    ///
+    /// assert_eq!(s2.rev_iter().collect::<Vec<_>>(), vec![&"login", &"logout"]);
+    /// ```
+    pub fn rev_iter(&'a self) -> Rev<BufferedIter<'a, T>> {
+        self.iter_buffered().rev()
+    }
+}
+
+/// The buffered iterator behavior implementation
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> Iterator for BufferedIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.buffer.size_hint()
+    }
+}
+
+/// The buffered iterator yields the elements from the bottom, too
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> DoubleEndedIterator for BufferedIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.buffer.next_back()
+    }
+}
+
+/// The number of buffered elements is known
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> ExactSizeIterator for BufferedIter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use core::ptr;
+    use std::string::String;
+    use std::vec::Vec;
+    use super::super::{Seq, SeqIterator};
+    use super::super::empty;
+
+    #[test]
+    fn test_iter() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!(s.iter_copied().sum::<u32>(), 6);
+
+        seqdef!(t; empty() => String::from("a"), String::from("b"));
+        let strings: Vec<String> = t.iter_cloned().collect();
+        assert_eq!(strings, vec!["b", "a"]);
+        assert_eq!(empty::<u32>().iter_copied().count(), 0);
+    }
+
+    #[test]
+    fn test_tails() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        let tails: Vec<&Seq<u32>> = s.tails().collect();
+        assert_eq!(tails.len(), 4);
+        assert!(std::ptr::eq(tails[0], &s));
+        assert_eq!(tails[1].head(), Some(&2));
+        assert_eq!(tails[2].head(), Some(&1));
+        assert!(tails[3].is_empty());
+
+        assert_eq!(empty::<u32>().tails().count(), 1);
+    }
+
+    #[test]
+    fn test_enumerate() {
+        seqdef!(s; empty() => "a", "b", "c");
+
+        let depths: Vec<(usize, &&str)> = s.enumerate_depth().collect();
+        assert_eq!(depths, vec![(0, &"c"), (1, &"b"), (2, &"a")]);
+
+        let heights: Vec<(usize, &&str)> = s.enumerate_height().collect();
+        assert_eq!(heights, vec![(2, &"c"), (1, &"b"), (0, &"a")]);
+
+        assert_eq!(empty::<u32>().enumerate_height().count(), 0);
+    }
+
+    #[test]
+    fn test_pairs() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        let pairs: Vec<(&u32, &u32)> = s.pairs().collect();
+        assert_eq!(pairs, vec![(&3, &2), (&2, &1)]);
+
+        seqdef!(t; empty() => 1u32);
+        assert_eq!(t.pairs().count(), 0);
+        assert_eq!(empty::<u32>().pairs().count(), 0);
+    }
+
+    #[test]
+    fn test_windows() {
+        seqdef!(s; empty() => 1u32, 2, 3, 4);
+
+        let windows: Vec<[&u32; 3]> = s.windows::<3>().collect();
+        assert_eq!(windows, vec![[&4, &3, &2], [&3, &2, &1]]);
+
+        assert_eq!(s.windows::<1>().count(), 4);
+        assert_eq!(s.windows::<4>().count(), 1);
+        assert_eq!(s.windows::<5>().count(), 0);
+    }
+
+    #[test]
+    fn test_intersperse() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        let items: Vec<u32> = s.intersperse(&0).cloned().collect();
+        assert_eq!(items, vec![3, 0, 2, 0, 1]);
+
+        seqdef!(t; empty() => 1u32);
+        assert_eq!(t.intersperse(&0).count(), 1);
+        assert_eq!(empty::<u32>().intersperse(&0).count(), 0);
+    }
+
+    #[test]
+    fn test_dedup() {
+        seqdef!(s; empty() => 1u32, 1, 2, 3, 3, 3, 1);
+
+        let items: Vec<u32> = s.dedup().cloned().collect();
+        assert_eq!(items, vec![1, 3, 2, 1]);
+
+        let items: Vec<u32> = s.dedup_by(|a, b| a % 2 == b % 2).cloned().collect();
+        assert_eq!(items, vec![1, 2, 1]);
+
+        assert_eq!(empty::<u32>().dedup().count(), 0);
+    }
+
+    #[cfg(feature = "unique")]
+    #[test]
+    fn test_unique() {
+        seqdef!(s; empty() => 1u32, 2, 1, 3, 2, 2);
+
+        let items: Vec<u32> = s.unique().cloned().collect();
+        assert_eq!(items, vec![2, 3, 1]);
+        assert_eq!(empty::<u32>().unique().count(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_buffered() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        let mut iter = s.iter_buffered();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), None);
+
+        assert_eq!(s.iter_buffered().rev().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(s.rev_iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(s.rev_iter().len(), 3);
+        assert_eq!(empty::<u32>().rev_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_iter_with_len() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        let mut iter = s.iter_with_len();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.by_ref().count(), 2);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+
+        let zipped = s.iter_with_known_len(3).zip(s.iter_with_len());
+        assert_eq!(zipped.len(), 3);
+        assert_eq!(empty::<u32>().iter_with_len().len(), 0);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "known length differs")]
+    fn test_iter_with_wrong_len() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+        let _ = s.iter_with_known_len(1);
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn test_iter_with_wrong_len() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+        let mut iter = s.iter_with_known_len(1);
+        assert_eq!(iter.by_ref().count(), 3);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(s.iter_with_known_len(5).count(), 3);
+    }
+
+    #[test]
+    fn test_budgeted() {
+        seqdef!(s; empty() => 1u32, 2, 3, 4, 5);
+
+        let mut iter = s.iter_budgeted(2);
+        assert_eq!(iter.by_ref().cloned().collect::<Vec<_>>(), vec![5, 4]);
+        assert_eq!(iter.budget(), 0);
+
+        let rest = iter.rest();
+        assert_eq!(rest.head(), Some(&3));
+
+        let mut iter = rest.iter_budgeted(10);
+        assert_eq!(iter.by_ref().cloned().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(iter.budget(), 7);
+        assert!(iter.rest().is_empty());
+    }
+
+    #[test]
+    fn test_budgeted_slice() {
+        let data = [1u32, 2, 3];
+        let s0 = Seq::ConsRef(0u32, empty());
+        let s = Seq::ConsSlice(&data, &s0);
+
+        let mut iter = s.iter_budgeted(2);
+        assert_eq!(iter.by_ref().cloned().collect::<Vec<_>>(), vec![3, 2]);
+        assert!(ptr::eq(iter.rest(), &s0)); // beneath the slice
+        assert!(iter.rest_iter().eq([1, 0].iter()));
+
+        let mut iter = iter.rest_iter().budgeted(10);
+        assert_eq!(iter.by_ref().count(), 2);
+        assert!(iter.rest().is_empty());
+        assert_eq!(iter.rest_iter().next(), None);
+    }
+
+    #[test]
+    fn test_sections() {
+        seqdef!(s; empty() => "fn main", "let", "fn run", "let", "let", "tmp");
+
+        let starts: Vec<&Seq<&str>> = s.sections(|ft| ft.starts_with("fn")).collect();
+        assert_eq!(starts.len(), 3);
+        assert_eq!(starts[0].head(), Some(&"tmp")); // the section on top of the last marker
+        assert_eq!(starts[1].head(), Some(&"fn run"));
+        assert_eq!(starts[1].len(), 3);
+        assert_eq!(starts[2].head(), Some(&"fn main"));
+        assert_eq!(starts[2].len(), 1);
+
+        assert_eq!(empty::<&str>().sections(|_| true).count(), 0);
+        assert_eq!(s.sections(|_| true).count(), s.len());
+    }
+
+    #[test]
+    fn test_sections_slice() {
+        let frames = ["fn main", "let", "fn run"];
+        let s0 = Seq::ConsRef("fn init", empty());
+        let s1 = Seq::ConsSlice(&frames, &s0);
+        let s = Seq::ConsRef("tmp", &s1);
+
+        let starts: Vec<&Seq<&str>> = s.sections(|ft| ft.starts_with("fn")).collect();
+        assert_eq!(starts.len(), 3);
+        assert!(ptr::eq(starts[1], &s1) && ptr::eq(starts[2], &s0));
+
+        let starts: Vec<SeqIterator<&str>> = s.section_iters(|ft| ft.starts_with("fn")).collect();
+        assert_eq!(starts.len(), 4);
+        assert_eq!(starts[0].clone().next(), Some(&"tmp"));
+        assert_eq!(starts[1].clone().next(), Some(&"fn run"));
+        assert_eq!(starts[2].clone().count(), 2);
+        assert_eq!(starts[3].clone().next(), Some(&"fn init"));
+        assert_eq!(empty::<&str>().section_iters(|_| true).count(), 0);
+    }
+}
@@ -0,0 +1,159 @@
+//! Protocol layers with the number of layers tracked by the type system.
+//!
+//! A [`Layered`] sequence of exactly `N` layers is extended to `N + 1` layers by
+//! [`push_layer`], the top most layer is removed by [`peel_layer`], yielding `N - 1` layers.
+//! The number of layers of the result is verified at compile time; pushing or peeling with a
+//! wrong number of layers is a build error, and so is peeling a layer from zero layers.
+//!
+//! # Example
+//! ```rust
+//! use seq::layered::Layered;
+//!
+//! let link: Layered<&str, 0> = Layered::new();
+//! let eth: Layered<&str, 1> = link.push_layer("ethernet");
+//! let ip: Layered<&str, 2> = eth.push_layer("ipv4");
+//! let tcp: Layered<&str, 3> = ip.push_layer("tcp");
+//!
+//! let (top, rest): (&&str, &Layered<&str, 2>) = tcp.peel_layer();
+//! assert_eq!(*top, "tcp");
+//! assert_eq!(rest.top(), &"ipv4");
+//! ```
+//! Peeling a layer from zero layers fails the build
+//! ```compile_fail
+//! use seq::layered::Layered;
+//!
+//! let link: Layered<&str, 0> = Layered::new();
+//! let (_top, _rest): (&&str, &Layered<&str, 0>) = link.peel_layer();
+//! ```
+//! [`Layered`]: struct.Layered.html
+//! [`push_layer`]: struct.Layered.html#method.push_layer
+//! [`peel_layer`]: struct.Layered.html#method.peel_layer
+
+use super::Seq;
+
+/// A sequence of exactly `N` layers of type `T`, the top most layer being the head
+#[repr(transparent)]
+pub struct Layered<'a, T: 'a, const N: usize> {
+    seq: Seq<'a, T>,
+}
+
+/// Layered method implementations for zero layers
+impl<'a, T: 'a> Layered<'a, T, 0> {
+    /// Returns the empty sequence of layers
+    pub fn new() -> Self {
+        Layered { seq: Seq::Empty }
+    }
+}
+
+/// By default there are zero layers
+impl<'a, T: 'a> Default for Layered<'a, T, 0> {
+    fn default() -> Self {
+        Layered::new()
+    }
+}
+
+/// Layered method implementations
+impl<'a, T: 'a, const N: usize> Layered<'a, T, N> {
+    /// The number of layers
+    pub const LAYERS: usize = N;
+
+    /// Returns a sequence of `M` layers, attaching the new top most `layer` to the existing
+    /// ones; `M` must equal `N + 1`
+    pub fn push_layer<const M: usize>(&'a self, layer: T) -> Layered<'a, T, M> {
+        const { assert!(M == N + 1, "pushing a layer must add exactly one layer") };
+        Layered { seq: Seq::ConsRef(layer, &self.seq) }
+    }
+
+    /// Returns the top most layer and the sequence of `M` layers beneath; `M` must equal `N - 1`
+    pub fn peel_layer<const M: usize>(&'a self) -> (&'a T, &'a Layered<'a, T, M>) {
+        const { assert!(N > 0 && M + 1 == N, "peeling a layer must remove exactly one layer") };
+        match self.seq.split_node() {
+            Some(([ref ft], rt)) => (ft, Layered::from_seq_unchecked(rt)),
+            _ => unreachable!("sequence of {} layers is not a node per layer", N),
+        }
+    }
+
+    /// Returns the top most layer, there must be one layer at least
+    pub fn top(&'a self) -> &'a T {
+        const { assert!(N > 0, "zero layers do not have a top most layer") };
+        match self.seq.head() {
+            Some(ft) => ft,
+            None => unreachable!("sequence of {} layers is empty", N),
+        }
+    }
+
+    /// Returns the underlying sequence of layers
+    pub fn as_seq(&self) -> &Seq<'a, T> {
+        &self.seq
+    }
+
+    /// Returns the sequence as `N` layers, if consisting of exactly `N` elements, each one being
+    /// a node of its own
+    pub fn try_from_seq(seq: &'a Seq<'a, T>) -> Option<&'a Layered<'a, T, N>> {
+        let mut cur = seq;
+        let mut nodes = 0;
+        while let Some((sl1, rt1)) = cur.split_node() {
+            // an empty slice node does not hold a layer, neither does a node of several
+            if sl1.len() != 1 || nodes == N {
+                return None;
+            }
+            nodes += 1;
+            cur = rt1;
+        }
+        if nodes == N {
+            Some(Layered::from_seq_unchecked(seq))
+        } else {
+            None
+        }
+    }
+
+    fn from_seq_unchecked(seq: &'a Seq<'a, T>) -> &'a Layered<'a, T, N> {
+        // Layered is a transparent wrapper of Seq
+        unsafe { &*(seq as *const Seq<'a, T> as *const Layered<'a, T, N>) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Layered;
+    use super::super::{empty, Seq};
+
+    #[test]
+    fn test_layers() {
+        let l0: Layered<u32, 0> = Layered::new();
+        let l1: Layered<u32, 1> = l0.push_layer(1);
+        let l2: Layered<u32, 2> = l1.push_layer(2);
+
+        assert_eq!(Layered::<u32, 2>::LAYERS, 2);
+        assert_eq!(l2.as_seq().len(), 2);
+        assert_eq!(l2.top(), &2);
+
+        let (top, rest): (&u32, &Layered<u32, 1>) = l2.peel_layer();
+        assert_eq!(*top, 2);
+        assert_eq!(rest.top(), &1);
+
+        let (top, rest): (&u32, &Layered<u32, 0>) = rest.peel_layer();
+        assert_eq!(*top, 1);
+        assert!(rest.as_seq().is_empty());
+    }
+
+    #[test]
+    fn test_try_from_seq() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+
+        assert!(Layered::<u32, 2>::try_from_seq(&s).is_none());
+        let l3 = Layered::<u32, 3>::try_from_seq(&s).unwrap();
+        assert_eq!(l3.top(), &2);
+
+        let data = [0u32, 1, 2];
+        let t = Seq::from(&data[..]);
+        assert!(Layered::<u32, 3>::try_from_seq(&t).is_none());
+
+        // an empty slice node must not pad the number of nodes
+        let pair = [1u32, 2];
+        let padded = Seq::ConsSlice(&pair, &Seq::ConsSlice(&[], empty()));
+        assert!(Layered::<u32, 2>::try_from_seq(&padded).is_none());
+        let single = Seq::ConsRef(1u32, &Seq::ConsSlice(&[], empty()));
+        assert!(Layered::<u32, 1>::try_from_seq(&single).is_none());
+    }
+}
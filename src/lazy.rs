@@ -0,0 +1,246 @@
+//! Lazy streams, computing the tail on demand.
+//!
+//! A [`Stream`] is a sequence whose tail is produced by a closure the first time it is
+//! accessed; the result is memoized, iterating the stream again does not invoke the closure a
+//! second time. Streams may be infinite, such as the stream of all natural numbers, as long as
+//! the consumer takes a finite prefix only.
+//!
+//! Iterating a stream by reference yields references to its elements, just like iterating a
+//! [`Seq`].
+//!
+//! # Example
+//! ```rust
+//! use seq::lazy::Stream;
+//!
+//! let mut n = 0u32;
+//! let naturals = Stream::from_fn(move || { n += 1; Some(n) });
+//!
+//! let evens: Vec<u32> = naturals.into_iter().filter(|x| *x % 2 == 0).take(3).cloned().collect();
+//! assert_eq!(evens, vec![2, 4, 6]);
+//! ```
+//!
+//! [`Stream`]: struct.Stream.html
+//! [`Seq`]: ../enum.Seq.html
+
+use alloc::boxed::Box;
+use core::cell::{Cell, OnceCell};
+use core::fmt;
+use core::iter::FusedIterator;
+
+/// The head and the tail of a non-empty stream
+type Node<'a, T> = Option<(T, Box<Stream<'a, T>>)>;
+
+/// The closure evaluating a stream
+type Thunk<'a, T> = Box<dyn FnOnce() -> Stream<'a, T> + 'a>;
+
+/// A lazy sequence of elements of type `T`, the tail being computed on first access
+pub struct Stream<'a, T: 'a> {
+    node: OnceCell<Node<'a, T>>,
+    thunk: Cell<Option<Thunk<'a, T>>>,
+}
+
+/// Stream method implementations
+impl<'a, T: 'a> Stream<'a, T> {
+    /// Returns the empty stream
+    pub fn empty() -> Stream<'a, T> {
+        Stream { node: OnceCell::from(None), thunk: Cell::new(None) }
+    }
+
+    /// Returns a stream attaching `head` to the evaluated stream `tail`
+    pub fn cons(head: T, tail: Stream<'a, T>) -> Stream<'a, T> {
+        Stream { node: OnceCell::from(Some((head, Box::new(tail)))), thunk: Cell::new(None) }
+    }
+
+    /// Returns a stream being evaluated by `f` on first access
+    pub fn lazy<F>(f: F) -> Stream<'a, T>
+        where F: FnOnce() -> Stream<'a, T> + 'a
+    {
+        Stream { node: OnceCell::new(), thunk: Cell::new(Some(Box::new(f))) }
+    }
+
+    /// Returns a stream of the elements produced by the generator `f`, invoked once per
+    /// element on demand; the stream ends as soon as `f` returns `None`
+    pub fn from_fn<F>(mut f: F) -> Stream<'a, T>
+        where F: FnMut() -> Option<T> + 'a
+    {
+        Stream::lazy(move || match f() {
+            Some(head) => Stream::cons(head, Stream::from_fn(f)),
+            None => Stream::empty(),
+        })
+    }
+
+    /// Returns the head and the tail, evaluating the stream if not done yet
+    fn force(&self) -> Option<&(T, Box<Stream<'a, T>>)> {
+        self.node.get_or_init(|| {
+            let thunk = self.thunk.take().expect("stream evaluated recursively");
+            // the evaluated stream is moved into this one, unlinking its tail
+            let mut evaluated = thunk();
+            evaluated.force();
+            evaluated.node.take().unwrap_or(None)
+        }).as_ref()
+    }
+
+    /// Returns reference to the first element, evaluating the stream if not done yet
+    pub fn head(&self) -> Option<&T> {
+        self.force().map(|node| &node.0)
+    }
+
+    /// Returns reference to the tail, evaluating the stream if not done yet; the tail itself
+    /// is evaluated on first access
+    pub fn tail(&self) -> Option<&Stream<'a, T>> {
+        self.force().map(|node| &*node.1)
+    }
+
+    /// Returns true if the stream is empty, evaluating the stream if not done yet
+    pub fn is_empty(&self) -> bool {
+        self.force().is_none()
+    }
+
+    /// Returns true if the stream has been evaluated already
+    pub fn is_evaluated(&self) -> bool {
+        self.node.get().is_some()
+    }
+
+    /// Returns an iterator over the elements, evaluating the stream element by element
+    pub fn iter(&self) -> StreamIterator<'_, 'a, T> {
+        StreamIterator { cur: Some(self) }
+    }
+}
+
+/// Dropping a stream unlinks the evaluated tails one by one in a loop, so dropping long
+/// streams does not overflow the stack
+impl<'a, T: 'a> Drop for Stream<'a, T> {
+    fn drop(&mut self) {
+        let mut next = self.node.take().flatten();
+        while let Some((_, mut rt)) = next {
+            // drops the previous element, its tail has been unlinked already
+            next = rt.node.take().flatten();
+        }
+    }
+}
+
+/// The default stream is the empty stream
+impl<'a, T: 'a> Default for Stream<'a, T> {
+    fn default() -> Self {
+        Stream::empty()
+    }
+}
+
+/// Debug format of the evaluated elements, the remainder not evaluated yet is marked by `..`
+impl<'a, T: fmt::Debug> fmt::Debug for Stream<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut cur = self;
+        loop {
+            match cur.node.get() {
+                Some(Some((ft, rt))) => {
+                    list.entry(ft);
+                    cur = rt;
+                }
+                Some(None) => break,
+                None => {
+                    list.entry(&format_args!(".."));
+                    break;
+                }
+            }
+        }
+        list.finish()
+    }
+}
+
+/// The stream iterator, evaluating the stream element by element
+#[derive(Clone)]
+pub struct StreamIterator<'b, 'a: 'b, T: 'a> {
+    cur: Option<&'b Stream<'a, T>>,
+}
+
+impl<'b, 'a: 'b, T: 'a> Iterator for StreamIterator<'b, 'a, T> {
+    type Item = &'b T;
+
+    fn next(&mut self) -> Option<&'b T> {
+        match self.cur?.force() {
+            Some((ft, rt)) => {
+                self.cur = Some(rt);
+                Some(ft)
+            }
+            None => {
+                self.cur = None;
+                None
+            }
+        }
+    }
+}
+
+impl<'b, 'a: 'b, T: 'a> FusedIterator for StreamIterator<'b, 'a, T> {}
+
+impl<'b, 'a: 'b, T: 'a> IntoIterator for &'b Stream<'a, T> {
+    type Item = &'b T;
+    type IntoIter = StreamIterator<'b, 'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stream;
+    use std::cell::Cell;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_stream() {
+        let s = Stream::cons(2u32, Stream::cons(1, Stream::empty()));
+        assert_eq!(s.head(), Some(&2));
+        assert_eq!(s.tail().and_then(|rt| rt.head()), Some(&1));
+        assert!(s.tail().and_then(|rt| rt.tail()).unwrap().is_empty());
+        assert_eq!(s.iter().cloned().collect::<Vec<_>>(), vec![2, 1]);
+        assert!(Stream::<u32>::default().is_empty());
+    }
+
+    #[test]
+    fn test_stream_memoized() {
+        let calls = Cell::new(0);
+        let mut n = 0u32;
+        let s = Stream::from_fn(|| {
+            calls.set(calls.get() + 1);
+            n += 1;
+            if n <= 3 { Some(n) } else { None }
+        });
+        assert!(!s.is_evaluated());
+        assert_eq!(format!("{:?}", s), "[..]");
+
+        assert_eq!(s.iter().take(2).cloned().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(format!("{:?}", s), "[1, 2, ..]");
+
+        assert_eq!(s.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(s.iter().count(), 3);
+        assert_eq!(calls.get(), 4);
+        assert_eq!(format!("{:?}", s), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn test_stream_infinite() {
+        fn naturals<'a>(from: u64) -> Stream<'a, u64> {
+            Stream::lazy(move || Stream::cons(from, naturals(from + 1)))
+        }
+
+        let s = naturals(0);
+        assert_eq!(s.iter().nth(1000), Some(&1000));
+
+        let mut iter = s.iter();
+        assert_eq!(iter.by_ref().take(200_000).count(), 200_000);
+        assert_eq!(iter.next(), Some(&200_000));
+        // dropping the evaluated prefix does not overflow the stack
+        drop(s);
+    }
+
+    #[test]
+    fn test_stream_lazy_tail() {
+        let s = Stream::cons(1u32, Stream::lazy(|| Stream::cons(2, Stream::empty())));
+        assert!(!s.tail().unwrap().is_evaluated());
+        assert_eq!((&s).into_iter().last(), Some(&2));
+        assert!(s.tail().unwrap().is_evaluated());
+    }
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "benchmark", feature(test))]
+#![no_std]
  
 //! The module `seq` provides the lightweight, generic sequence container [`Seq`] for unmovable data.
 //!
@@ -76,9 +76,60 @@
 //! [`head`]:  #method.head
 //! [`Seq`]: enum.Seq.html
 
-use std::fmt;
-use std::iter::Iterator;
-
+#[cfg(any(feature = "std", test))]
+#[cfg_attr(test, macro_use(format, println, vec))]
+extern crate std;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "im")]
+extern crate im;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+#[cfg(feature = "rpds")]
+extern crate rpds;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "arena")]
+extern crate bumpalo;
+#[cfg(feature = "macros")]
+extern crate seq_macros;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "log")]
+extern crate log;
+#[cfg(test)]
+extern crate serde_json;
+
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "arc")]
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FusedIterator, Iterator};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+#[cfg(any(all(feature = "alloc", not(feature = "lite-seq")), feature = "arc"))]
+use core::mem;
+use core::ptr;
+use core::slice;
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
+
+
+/// True, if this build supports the boxed [`ConsOwn`] elements; false if feature `lite-seq` is
+/// enabled or feature `alloc` is disabled. The fallible constructors, such as [`try_push_owned`], depend on it.
+///
+/// [`ConsOwn`]: enum.Seq.html#variant.ConsOwn
+/// [`try_push_owned`]: enum.Seq.html#method.try_push_owned
+pub const OWNED_NODES: bool = cfg!(all(feature = "alloc", not(feature = "lite-seq")));
 
 /// A single-ended, growable, unmovable queue of data, linking constant data with dynamic data.
 ///
@@ -132,51 +183,309 @@ use std::iter::Iterator;
 ///    return seq.into_iter().fold(0, |x, y| x + y);
 /// }
 /// ```
+///
+/// # Threads
+///
+/// A sequence is `Send` and `Sync` if the element type is. With feature `arc` enabled, the
+/// tails of [`ConsArc`] nodes are shared between threads, for this the element type must be both
+/// `Send` and `Sync`. Fully-owned sequences of `ConsArc` nodes are handed to other threads
+/// without copying:
+/// ```rust
+/// # #[cfg(feature = "arc")]
+/// # fn main() {
+/// use std::sync::Arc;
+/// use std::thread;
+/// use seq::Seq;
+///
+/// let history: Arc<Seq<'static, u32>> = Arc::new(Seq::ConsArc(1, Arc::new(Seq::Empty)));
+/// let snapshot = history.clone();
+/// thread::spawn(move || assert_eq!(snapshot.head(), Some(&1))).join().unwrap();
+/// # }
+/// # #[cfg(not(feature = "arc"))]
+/// # fn main() {}
+/// ```
+///
+/// # Variants
+///
+/// The enum is non-exhaustive, variants may be added in future releases. Matching a sequence
+/// outside this crate requires a wildcard arm; the constructors [`cons`] and [`cons_own`] and the
+/// deconstructor [`split_node`] cover all variants.
+/// ```rust
+/// use seq::Seq;
+///
+/// fn top<'a>(seq: &'a Seq<'a, u32>) -> Option<&'a u32> {
+///    match *seq {
+///       Seq::ConsRef(ref ft, _) => Some(ft),
+///       _ => seq.split_node().and_then(|(sl, _)| sl.last()),
+///    }
+/// }
+///
+/// let s1 = Seq::cons(1, seq::empty());
+/// let s2 = Seq::cons(2, &s1);
+/// assert_eq!(top(&s2), Some(&2));
+/// ```
 /// [`Empty`]: enum.Seq.html#variant.Empty
 /// [`ConsRef`]: enum.Seq.html#variant.ConsRef
+/// [`ConsArc`]: enum.Seq.html#variant.ConsArc
 /// [`tail`]:  #method.tail
 /// [`head`]:  #method.head
-#[derive(Clone)]
+/// [`cons`]:  #method.cons
+/// [`cons_own`]:  #method.cons_own
+/// [`split_node`]:  #method.split_node
+#[non_exhaustive]
 pub enum Seq<'a, T: 'a> {
     /// The empty sequence
     Empty,
     /// Constructing a sequence with head data and reference to a tail
     ConsRef(T, &'a Seq<'a, T>),
     /// Constructing a sequence with head data and reference to boxed tail
-    #[cfg(not(feature = "lite-seq"))]
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
     ConsOwn(T, Box<Seq<'a, T>>),
+    /// Constructing a sequence with the elements of a slice and reference to a tail, linking the
+    /// slice as a single node without copying; the last element of the slice is the head.
+    ///
+    /// **The node is not element-granular.** The [`tail`] of the node is the sequence beneath
+    /// the whole slice, not the slice without its head: for a slice of several elements
+    /// `s.tail().unwrap().len() == s.len() - 1` does not hold, nor do [`tails`] and the
+    /// node-level continuations stop inside the slice. The iterators and [`len`] count every
+    /// element.
+    ///
+    /// [`tail`]: #method.tail
+    /// [`tails`]: #method.tails
+    /// [`len`]: #method.len
+    ConsSlice(&'a [T], &'a Seq<'a, T>),
+    /// Constructing a sequence with an inline chunk of elements and reference to a tail; the
+    /// last element of the chunk is the head
+    #[cfg(feature = "chunk")]
+    ConsChunk(chunk::Chunk<T>, &'a Seq<'a, T>),
+    /// Constructing a sequence with head data and an atomically reference-counted tail, which
+    /// may be shared by several sequences, across threads, too
+    #[cfg(feature = "arc")]
+    ConsArc(T, Arc<Seq<'a, T>>),
 }
 
 
+/// Declares the accessor `const fn`, unless feature `arc` is enabled: the reference-counted
+/// tails can not be dereferenced in const contexts
+macro_rules! const_unless_arc {
+    ($(#[$attr:meta])* pub fn $($rest:tt)*) => {
+        #[cfg(not(feature = "arc"))]
+        $(#[$attr])* pub const fn $($rest)*
+        #[cfg(feature = "arc")]
+        $(#[$attr])* pub fn $($rest)*
+    };
+}
+
 /// Seq method implementations
 impl<'a, T: 'a> Seq<'a, T> {
-    /// Returns a reference to the head-element
-    pub fn head(&'a self) -> Option<&'a T> {
-        match self {
-            &Seq::Empty => Option::None,
-            &Seq::ConsRef(ref ft1, _) => Option::Some(&*ft1),
-            #[cfg(not(feature = "lite-seq"))]
-            &Seq::ConsOwn(ref ft1, _) => Option::Some(&*ft1),
+    const_unless_arc! {
+        /// Returns a reference to the head-element; a const fn, unless feature `arc` is enabled
+        pub fn head(&'a self) -> Option<&'a T> {
+            let mut cur = self;
+            while let Some((sl1, rt1)) = cur.split_node() {
+                if let Some(ft1) = sl1.last() {
+                    return Some(ft1);
+                }
+                cur = rt1;
+            }
+            None
+        }
+    }
+
+    const_unless_arc! {
+        /// Returns reference to the tail, the sequence beneath the top most node; a const fn,
+        /// unless feature `arc` is enabled.
+        ///
+        /// **The tail is not element-granular for multi-element nodes.** The tail of a
+        /// [`ConsSlice`] or `ConsChunk` node is the sequence beneath the whole node, dropping all
+        /// of its elements, not just the head; `s.tail().unwrap().len() == s.len() - 1` holds
+        /// for single-element nodes only; the tail of an empty slice node is of the same length
+        /// as the node itself. Iterate the sequence to visit every element.
+        ///
+        /// ```rust
+        /// use seq::Seq;
+        ///
+        /// let data = [1, 2, 3];
+        /// let s = Seq::ConsSlice(&data, seq::empty());
+        /// assert_eq!(s.len(), 3);
+        /// assert_eq!(s.tail().unwrap().len(), 0);
+        /// assert_eq!(s.iter().nth(1), Some(&2));
+        /// ```
+        ///
+        /// [`ConsSlice`]: enum.Seq.html#variant.ConsSlice
+        pub fn tail(&'a self) -> Option<&'a Seq<'a, T>> {
+            match self.split_node() {
+                Some((_, rt1)) => Some(rt1),
+                None => None,
+            }
+        }
+    }
+
+    const_unless_arc! {
+        /// Splits the top most node into its elements and the sequence beneath, unless empty. The
+        /// head is the last element of the slice; nodes other than [`ConsSlice`] and `ConsChunk`
+        /// hold a single element. A const fn, unless feature `arc` is enabled.
+        ///
+        /// [`ConsSlice`]: enum.Seq.html#variant.ConsSlice
+        pub fn split_node(&'a self) -> Option<(&'a [T], &'a Seq<'a, T>)> {
+            #[cfg(feature = "arc")]
+            if let Seq::ConsArc(ref ft1, ref rt1) = *self {
+                return Option::Some((slice::from_ref(ft1), &**rt1));
+            }
+            self.__split_node()
+        }
+    }
+
+    /// Splits the top most node at compile time, used by the assertion macros, not part of the
+    /// API. The reference-counted `ConsArc` nodes can not be created at compile time, the
+    /// sequence ends there.
+    #[doc(hidden)]
+    pub const fn __split_node(&'a self) -> Option<(&'a [T], &'a Seq<'a, T>)> {
+        match *self {
+            Seq::Empty => Option::None,
+            Seq::ConsRef(ref ft1, rt1) => Option::Some((slice::from_ref(ft1), rt1)),
+            #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+            Seq::ConsOwn(ref ft1, ref rt1) => Option::Some((slice::from_ref(ft1), &**rt1)),
+            Seq::ConsSlice(sl1, rt1) => Option::Some((sl1, rt1)),
+            #[cfg(feature = "chunk")]
+            Seq::ConsChunk(ref ch1, rt1) => Option::Some((ch1.as_slice(), rt1)),
+            #[cfg(feature = "arc")]
+            Seq::ConsArc(..) => Option::None,
+        }
+    }
+
+    const_unless_arc! {
+        /// Returns the number of elements, walking the sequence in a loop; a const fn, unless
+        /// feature `arc` is enabled. The length of const and static sequences can be checked at
+        /// compile time, see [`assert_seq_len_eq!`](macro.assert_seq_len_eq.html).
+        pub fn len(&'a self) -> usize {
+            let mut len = 0;
+            let mut cur = self;
+            while let Some((sl1, rt1)) = cur.split_node() {
+                len += sl1.len();
+                cur = rt1;
+            }
+            len
         }
     }
 
-    /// Returns reference to the tail
-    pub fn tail(&'a self) -> Option<&'a Seq<T>> {
-        match self {
-            &Seq::Empty => Option::None,
-            &Seq::ConsRef(_, ref rt1) => Option::Some(*rt1),
-            #[cfg(not(feature = "lite-seq"))]
-            &Seq::ConsOwn(_, ref rt1) => Option::Some(&**rt1),
+    const_unless_arc! {
+        /// Returns true if the sequence is empty; a const fn, unless feature `arc` is enabled
+        pub fn is_empty(&'a self) -> bool {
+            self.head().is_none()
+        }
+    }
+}
 
+/// Seq method implementations
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Panics if the sequence is deeper than [`MAX_SAFE_DEPTH`], the length up to which the
+    /// recursive operations compiled into this build are guaranteed not to overflow the stack.
+    ///
+    /// The check walks at most `MAX_SAFE_DEPTH` nodes, it terminates for cyclic sequences, too.
+    /// Call it during startup validation for sequences of unbounded length.
+    ///
+    /// [`MAX_SAFE_DEPTH`]: limits/constant.MAX_SAFE_DEPTH.html
+    pub fn assert_traversal_safe(&'a self) {
+        if limits::MAX_SAFE_DEPTH != usize::MAX
+            && self.into_iter().nth(limits::MAX_SAFE_DEPTH).is_some() {
+            panic!("sequence exceeds the safe traversal depth of {} elements",
+                   limits::MAX_SAFE_DEPTH);
         }
     }
 
-    pub fn len(&'a self) -> usize {
-         match self {
-            &Seq::Empty => 0,
-             &Seq::ConsRef(_, ref rt1) => 1 + rt1.len(),
-            #[cfg(not(feature = "lite-seq"))]
-             &Seq::ConsOwn(_, ref rt1) => 1 + rt1.len(),
+    /// Returns a new sequence attaching `value` as head to this sequence, which becomes the boxed
+    /// tail. Without support for boxed elements (feature `lite-seq`, or no `alloc`) the value and the sequence
+    /// are handed back as error, permitting a single code path for both configurations.
+    pub fn try_push_owned(self, value: T) -> Result<Seq<'a, T>, (T, Seq<'a, T>)> {
+        #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+        return Ok(Seq::ConsOwn(value, Box::new(self)));
+        #[cfg(any(feature = "lite-seq", not(feature = "alloc")))]
+        return Err((value, self));
+    }
+
+    /// Returns a sequence of boxed elements, the last element of the vector becoming the head.
+    /// Without support for boxed elements (feature `lite-seq`) the vector is handed back as error.
+    #[cfg(feature = "alloc")]
+    pub fn try_from_vec(vec: Vec<T>) -> Result<Seq<'a, T>, Vec<T>> {
+        #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+        return Ok(Seq::from(vec));
+        #[cfg(feature = "lite-seq")]
+        return Err(vec);
+    }
+}
+
+/// Seq constructor implementations
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns a new sequence attaching `head` to the borrowed `tail`, a `ConsRef` node
+    pub const fn cons(head: T, tail: &'a Seq<'a, T>) -> Seq<'a, T> {
+        Seq::ConsRef(head, tail)
+    }
+
+    /// Returns a new sequence attaching `head` to the boxed `tail`, a `ConsOwn` node
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    pub fn cons_own(head: T, tail: Box<Seq<'a, T>>) -> Seq<'a, T> {
+        Seq::ConsOwn(head, tail)
+    }
+
+    /// Returns a new sequence attaching the elements of `slice` to the borrowed `tail`, a
+    /// `ConsSlice` node; the last element of the slice becomes the head
+    pub const fn cons_slice(slice: &'a [T], tail: &'a Seq<'a, T>) -> Seq<'a, T> {
+        Seq::ConsSlice(slice, tail)
+    }
+}
+
+/// Seq scoped builder method implementations
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Calls `f` with the sequence extended by the head `value`, a `ConsRef` node living for the
+    /// duration of the call only
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// fn depth(scope: &Seq<&str>, n: usize) -> usize {
+    ///     match n {
+    ///         0 => scope.len(),
+    ///         _ => scope.with_pushed("frame", |inner| depth(inner, n - 1)),
+    ///     }
+    /// }
+    /// assert_eq!(depth(seq::empty(), 3), 3);
+    /// ```
+    pub fn with_pushed<R, F>(&'a self, value: T, f: F) -> R
+        where F: FnOnce(&Seq<'_, T>) -> R
+    {
+        f(&Seq::ConsRef(value, self))
+    }
+
+    /// Calls `f` with the sequence extended by the items of `iter`, the last item being the head;
+    /// each item lives in a stack frame of its own for the duration of the call
+    pub fn with_pushed_all<I, R, F>(&'a self, iter: I, f: F) -> R
+        where I: IntoIterator<Item = T>, F: FnOnce(&Seq<'_, T>) -> R
+    {
+        attach_with(&mut iter.into_iter(), self, f)
+    }
+}
+
+/// Seq copy-on-write method implementations
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+impl<'a, T: Clone> Seq<'a, T> {
+    /// Returns a new sequence attaching `value` as head to a borrowed or owned tail, becoming a
+    /// `ConsRef` or `ConsOwn` node, respectively. Functions accepting a `Cow` tail need a single
+    /// code path for both, and clone the tail only if they decide to rebuild it.
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use seq::Seq;
+    ///
+    /// let base: Seq<u32> = Seq::ConsRef(1, &Seq::Empty);
+    /// let borrowed = Seq::cons_cow(2, Cow::Borrowed(&base));
+    /// let owned = Seq::cons_cow(2, Cow::Owned(base.clone()));
+    /// assert!(matches!(borrowed, Seq::ConsRef(..)));
+    /// assert!(matches!(owned, Seq::ConsOwn(..)));
+    /// assert_eq!(borrowed, owned);
+    /// ```
+    pub fn cons_cow(value: T, tail: Cow<'a, Seq<'a, T>>) -> Seq<'a, T> {
+        match tail {
+            Cow::Borrowed(rt) => Seq::ConsRef(value, rt),
+            Cow::Owned(rt) => Seq::ConsOwn(value, Box::new(rt)),
         }
     }
 }
@@ -193,21 +502,100 @@ impl<'a, T: 'a> Seq<'a, T> {
 ///
 /// Example 3) Creating a seq variable u, using Seq `s` as tail of example 1.
 /// `seqdef!(u; &s => 3, 4, 5);`
+///
+/// Example 4) Creating a seq variable v of the data item 0 repeated 4 times, on top of `u`.
+/// `seqdef!(v; &u => repeat 0; 4);`
+///
+/// Example 5) Creating a seq variable w of the array elements, the last one being the top most.
+/// `seqdef!(w; from &data);`
+///
+/// Example 6) Creating a seq variable x where the first data item 0 is the top most, on top of `u`.
+/// `seqdef!(x; &u => rev 0, 1, 2);`
+///
+/// The data list may end with a trailing comma. The repeated data items and the array elements
+/// are attached as one `ConsRef` node per element, like the items of a data list; they must be
+/// `Copy`, and the length of the array must be known at compile time and not be zero. An array
+/// expression in the data list, such as `[0u8; 4]`, is a single data item.
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// # fn main() {
+/// let data = [0, 1, 2];
+/// seqdef!(s; from &data);
+/// seqdef!(t; 0, 1, 2,);
+/// assert_eq!(s, t);
+/// assert_eq!(s.tail(), t.tail());
+///
+/// seqdef!(u; &s => repeat 7; 2);
+/// assert_eq!(u, [7, 7, 2, 1, 0][..]);
+///
+/// seqdef!(x; rev 2, 1, 0);
+/// assert_eq!(x, t);
+/// # }
+/// ```
 #[macro_export]
 macro_rules! seqdef {
 
-   ($id:ident; $($ftx:expr),* ) => {
+   (@rev $id:ident; [$($acc:expr),*];) => {
+        $crate::seqdef!($id; $($acc),*);
+   };
+
+   (@rev $id:ident; [$($acc:expr),*]; $ft:expr $(, $ftx:expr)*) => {
+        $crate::seqdef!(@rev $id; [$ft $(, $acc)*]; $($ftx),*);
+   };
+
+   (@rev $id:ident; $rt:expr => [$($acc:expr),*];) => {
+        $crate::seqdef!($id; $rt => $($acc),*);
+   };
+
+   (@rev $id:ident; $rt:expr => [$($acc:expr),*]; $ft:expr $(, $ftx:expr)*) => {
+        $crate::seqdef!(@rev $id; $rt => [$ft $(, $acc)*]; $($ftx),*);
+   };
+
+   (@nodes $id:ident; $rt:expr => $items:expr) => {
+        let mut $id = ($rt, $items, $crate::ConsRefNodes::new());
+        let $id = $id.2.link($id.1, $id.0);
+   };
+
+   ($id:ident; rev $($ftx:expr),* $(,)?) => {
+        $crate::seqdef!(@rev $id; []; $($ftx),*);
+   };
+
+   ($id:ident; $rt:expr => rev $ft0:expr $(, $ftx:expr)* $(,)?) => {
+        $crate::seqdef!(@rev $id; $rt => [$ft0]; $($ftx),*);
+   };
+
+   ($id:ident; repeat $ft:expr; $n:expr $(,)?) => {
+        let $id = $crate::Seq::Empty;
+        $crate::seqdef!(@nodes $id; &$id => [$ft; $n]);
+   };
+
+   ($id:ident; from $sl:expr $(,)?) => {
+        let $id = $crate::Seq::Empty;
+        $crate::seqdef!(@nodes $id; &$id => *$sl);
+   };
+
+   ($id:ident; $rt:expr => repeat $ft:expr; $n:expr $(,)?) => {
+        $crate::seqdef!(@nodes $id; $rt => [$ft; $n]);
+   };
+
+   ($id:ident; $rt:expr => from $sl:expr $(,)?) => {
+        $crate::seqdef!(@nodes $id; $rt => *$sl);
+   };
+
+   ($id:ident; $($ftx:expr),* $(,)?) => {
         let $id =  $crate::Seq::Empty;
         $(
         let $id =  $crate::Seq::ConsRef( $ftx, & $id );
         )*
    };
 
-   ($id:ident; $rt:expr => $ft:expr ) => {
+   ($id:ident; $rt:expr => $ft:expr $(,)?) => {
         let $id =  $crate::Seq::ConsRef( $ft, $rt );
    };
 
-   ($id:ident; $rt:expr => $ft0:expr, $($ftx:expr),* ) => {
+   ($id:ident; $rt:expr => $ft0:expr, $($ftx:expr),* $(,)?) => {
         let $id =  $crate::Seq::ConsRef( $ft0, $rt );
         $(
         let $id =  $crate::Seq::ConsRef( $ftx, & $id );
@@ -215,47 +603,777 @@ macro_rules! seqdef {
    };
 }
 
+/// The seqref! macro is an expression referencing a stack-allocated sequence of the specified
+/// data list, the last data item in the list will be the top most in the sequence. The elements
+/// are temporaries, living until the end of the enclosing statement; other than `seqdef!` the
+/// sequence is passed to a function call directly, without a named binding.
+///
+/// Example) Passing sequences to a function, the second one attached onto the tail `s`
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// fn sum(seq: &Seq<u32>) -> u32 {
+///     seq.into_iter().sum()
+/// }
+///
+/// # fn main() {
+/// assert_eq!(sum(seqref![0, 1, 2]), 3);
+///
+/// seqdef!(s; 0, 1, 2);
+/// assert_eq!(sum(seqref![&s => 3, 4]), 10);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! seqref {
+    (@acc $rt:expr;) => {
+        $rt
+    };
+
+    (@acc $rt:expr; $ft:expr $(, $ftx:expr)*) => {
+        $crate::seqref!(@acc &$crate::Seq::ConsRef($ft, $rt); $($ftx),*)
+    };
+
+    ($rt:expr => $($ftx:expr),+ $(,)?) => {
+        $crate::seqref!(@acc $rt; $($ftx),+)
+    };
+
+    ($($ftx:expr),* $(,)?) => {
+        $crate::seqref!(@acc &$crate::Seq::Empty; $($ftx),*)
+    };
+}
+
+/// The seq_collect! macro consumes the items of an iterator at runtime, attaching each one as
+/// `ConsRef` element onto the stack, and calls the closure with the sequence, the last item
+/// being the head. The sequence is valid inside the closure only; building it does not
+/// allocate, but takes one stack frame per item.
+///
+/// Example) Summing up dynamically sized input
+/// ```rust
+/// #[macro_use] extern crate seq;
+///
+/// # fn main() {
+/// let input = "3 1 4";
+/// let sum = seq_collect!(input.split(' ').map(|w| w.parse::<u32>().unwrap()) => |s| {
+///     assert_eq!(s.head(), Some(&4));
+///     s.into_iter().sum::<u32>()
+/// });
+/// assert_eq!(sum, 8);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! seq_collect {
+    ($iter:expr => $f:expr) => {
+        $crate::collect_with($iter, $f)
+    };
+}
+
+/// The seq! macro creates a sequence of boxed `ConsOwn` elements for the specified data list, the
+/// last data item in the list will be the top most in the sequence. Other than `seqdef!` the
+/// macro is an expression of type `Seq<'static, T>`, to be returned by functions or stored in
+/// struct fields.
+///
+/// Example) Returning the sequence of the data items 0, 1, 2, where 2 is the top most data item
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// fn numbers() -> Seq<'static, u32> {
+///     seq![0, 1, 2]
+/// }
+///
+/// # fn main() {
+/// assert_eq!(numbers().head(), Some(&2));
+/// assert_eq!(numbers(), seq![0, 1, 2,]);
+/// # }
+/// ```
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+#[macro_export]
+macro_rules! seq {
+    ($($ftx:expr),* $(,)?) => {
+        $crate::Seq::from([$($ftx),*])
+    };
+}
+
+/// The static_seq! macro declares static or const sequences for the specified data lists, the
+/// last data item in a list will be the top most in the sequence. The nodes beneath the top most
+/// one are nested in the initializer, placed in static memory by the compiler; optionally the
+/// data list is attached onto another static sequence, sharing it as tail.
+///
+/// Example) Declaring a lookup table, and a second one extending it
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// static_seq! {
+///     static PRIMES: Seq<u32> = [7, 5, 3, 2];
+///     pub static MORE_PRIMES: Seq<u32> = &PRIMES => [11, 13,];
+///     const NONE: Seq<u32> = [];
+/// }
+/// assert_seq_len_eq!(MORE_PRIMES, 6);
+///
+/// # fn main() {
+/// assert_eq!(PRIMES, [2, 3, 5, 7][..]);
+/// assert_eq!(MORE_PRIMES.head(), Some(&13));
+/// assert!(NONE.is_empty());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! static_seq {
+    (@nest $rt:expr;) => {
+        $rt
+    };
+
+    (@nest $rt:expr; $ft:expr $(, $ftx:expr)*) => {
+        $crate::static_seq!(@nest $crate::Seq::ConsRef($ft, &$rt); $($ftx),*)
+    };
+
+    () => {};
+
+    ($(#[$attr:meta])* $vis:vis static $id:ident: $ty:ty = [$($ftx:expr),* $(,)?]; $($rest:tt)*) => {
+        $(#[$attr])* $vis static $id: $ty = $crate::static_seq!(@nest $crate::Seq::Empty; $($ftx),*);
+        $crate::static_seq!($($rest)*);
+    };
+
+    ($(#[$attr:meta])* $vis:vis static $id:ident: $ty:ty = $rt:expr => [$ft0:expr $(, $ftx:expr)* $(,)?];
+     $($rest:tt)*) => {
+        $(#[$attr])* $vis static $id: $ty =
+            $crate::static_seq!(@nest $crate::Seq::ConsRef($ft0, $rt); $($ftx),*);
+        $crate::static_seq!($($rest)*);
+    };
+
+    ($(#[$attr:meta])* $vis:vis const $id:ident: $ty:ty = [$($ftx:expr),* $(,)?]; $($rest:tt)*) => {
+        $(#[$attr])* $vis const $id: $ty = $crate::static_seq!(@nest $crate::Seq::Empty; $($ftx),*);
+        $crate::static_seq!($($rest)*);
+    };
+
+    ($(#[$attr:meta])* $vis:vis const $id:ident: $ty:ty = $rt:expr => [$ft0:expr $(, $ftx:expr)* $(,)?];
+     $($rest:tt)*) => {
+        $(#[$attr])* $vis const $id: $ty =
+            $crate::static_seq!(@nest $crate::Seq::ConsRef($ft0, $rt); $($ftx),*);
+        $crate::static_seq!($($rest)*);
+    };
+}
+
+/// The static_seqs! macro declares long static sequences, generated at compile time by the
+/// procedural macro of crate `seq-macros`, enabled by feature `macros`. The elements are given
+/// by a literal list, by an inclusive integer range, its bounds being literals, negated or not,
+/// or by a file of comma separated elements, its path relative to the manifest directory; the
+/// last element will be the top most in the sequence. Every node is a static item of its own, so
+/// the length is not bound by the recursion limit; sequences declared by the same invocation
+/// share their common tails, a declared sequence being the very node other ones are built on.
+///
+/// Example) Declaring tables sharing their tails
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// static_seqs! {
+///     static SQUARES: Seq<u32> = [0, 1, 4, 9, 16];
+///     static SQUARES_LE_9: Seq<u32> = [0, 1, 4, 9];
+///     pub static COUNTER: Seq<u16> = 1..=1000;
+///     static OFFSETS: Seq<i32> = -5..=5;
+/// }
+///
+/// # fn main() {
+/// assert_eq!(SQUARES.head(), Some(&16));
+/// // the tail of the squares table is the table of the squares up to 9
+/// assert!(std::ptr::eq(SQUARES.tail().unwrap(), &SQUARES_LE_9));
+/// assert_eq!(COUNTER.len(), 1000);
+/// assert_eq!(OFFSETS.into_iter().last(), Some(&-5));
+/// # }
+/// ```
+/// The file included holds the elements as Rust expressions, separated by commas:
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// static_seqs! {
+///     static FIB: Seq<u64> = include!("testdata/fib.txt");
+/// }
+///
+/// # fn main() {
+/// assert_eq!(FIB.head(), Some(&21));
+/// # }
+/// ```
+#[cfg(feature = "macros")]
+#[macro_export]
+macro_rules! static_seqs {
+    ($($decl:tt)*) => {
+        $crate::__static_seqs!($crate; $($decl)*);
+    };
+}
+
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use seq_macros::static_seqs as __static_seqs;
+
+/// The seq_event! macro records a `tracing` event, the names of the spans of the context stack
+/// being attached as field `context`, root first. Enabled by feature `tracing`.
+///
+/// Example) Recording the span context of a recursive parser
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// extern crate tracing;
+/// use seq::Seq;
+/// use tracing::{span, Level, Span};
+///
+/// fn parse(ctx: &Seq<Span>, input: &str) {
+///     seq::trace::in_span(ctx, span!(Level::DEBUG, "parse"), |ctx| {
+///         seq_event!(Level::DEBUG, ctx, "parsing {}", input);
+///     })
+/// }
+/// # fn main() { parse(seq::empty(), "1 + 2"); }
+/// ```
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! seq_event {
+    ($lvl:expr, $ctx:expr, $($arg:tt)+) => {
+        $crate::__tracing::event!($lvl, context = %$crate::trace::SpanNames::new($ctx), $($arg)+)
+    };
+}
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub extern crate tracing as __tracing;
+
+/// The seq_log! macro logs a `log` record, its message prefixed by the breadcrumb of the context
+/// sequence, root first; without any scope the message is logged as is. Enabled by feature
+/// `log`, the macros `seq_error!`, `seq_warn!`, `seq_info!`, `seq_debug!` and `seq_trace!` log
+/// at the respective level.
+///
+/// Example) Logging the scopes of a recursive descent
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// extern crate log;
+/// use seq::Seq;
+///
+/// fn check(ctx: &Seq<&str>, depth: u32) {
+///     if depth == 0 {
+///         seq_log!(log::Level::Warn, ctx, "limit reached");
+///         return;
+///     }
+///     check(&Seq::ConsRef("level", ctx), depth - 1)
+/// }
+/// # fn main() { check(seq::empty(), 3); }
+/// ```
+#[cfg(feature = "log")]
+#[macro_export]
+macro_rules! seq_log {
+    ($lvl:expr, $ctx:expr, $($arg:tt)+) => {{
+        let ctx = $ctx;
+        if ctx.is_empty() {
+            $crate::__log::log!($lvl, $($arg)+)
+        } else {
+            $crate::__log::log!($lvl, "{}: {}", $crate::logging::breadcrumb(ctx),
+                format_args!($($arg)+))
+        }
+    }};
+}
+
+/// Logs a record at level error, prefixed by the breadcrumb of the context sequence
+#[cfg(feature = "log")]
+#[macro_export]
+macro_rules! seq_error {
+    ($ctx:expr, $($arg:tt)+) => { $crate::seq_log!($crate::__log::Level::Error, $ctx, $($arg)+) };
+}
+
+/// Logs a record at level warn, prefixed by the breadcrumb of the context sequence
+#[cfg(feature = "log")]
+#[macro_export]
+macro_rules! seq_warn {
+    ($ctx:expr, $($arg:tt)+) => { $crate::seq_log!($crate::__log::Level::Warn, $ctx, $($arg)+) };
+}
+
+/// Logs a record at level info, prefixed by the breadcrumb of the context sequence
+#[cfg(feature = "log")]
+#[macro_export]
+macro_rules! seq_info {
+    ($ctx:expr, $($arg:tt)+) => { $crate::seq_log!($crate::__log::Level::Info, $ctx, $($arg)+) };
+}
+
+/// Logs a record at level debug, prefixed by the breadcrumb of the context sequence
+#[cfg(feature = "log")]
+#[macro_export]
+macro_rules! seq_debug {
+    ($ctx:expr, $($arg:tt)+) => { $crate::seq_log!($crate::__log::Level::Debug, $ctx, $($arg)+) };
+}
+
+/// Logs a record at level trace, prefixed by the breadcrumb of the context sequence
+#[cfg(feature = "log")]
+#[macro_export]
+macro_rules! seq_trace {
+    ($ctx:expr, $($arg:tt)+) => { $crate::seq_log!($crate::__log::Level::Trace, $ctx, $($arg)+) };
+}
+
+#[cfg(feature = "log")]
+#[doc(hidden)]
+pub extern crate log as __log;
+
+/// The assert_seq_len_eq! macro verifies at compile time, that the const or static sequence has
+/// the expected number of elements.
+///
+/// Example) Verifying the length of a lookup table
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// static PRIMES: Seq<u32> = Seq::ConsRef(2, &Seq::ConsRef(3, &Seq::ConsRef(5, &Seq::Empty)));
+/// assert_seq_len_eq!(PRIMES, 3);
+/// # fn main() {}
+/// ```
+/// A mismatch fails the build
+/// ```compile_fail
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// static PRIMES: Seq<u32> = Seq::ConsRef(2, &Seq::ConsRef(3, &Seq::ConsRef(5, &Seq::Empty)));
+/// assert_seq_len_eq!(PRIMES, 4);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_seq_len_eq {
+    ($seq:expr, $len:expr) => {
+        const _: () = {
+            let mut len = 0;
+            let mut cur = &$seq;
+            while let Some((sl, rt)) = cur.__split_node() {
+                len += sl.len();
+                cur = rt;
+            }
+            assert!(len == $len, "sequence length mismatch");
+        };
+    };
+}
+
+/// The assert_seq_sorted! macro verifies at compile time, that the elements of the const or
+/// static sequence are in ascending order, starting with the head. The elements must be of
+/// primitive type, as comparison of other types is not available at compile time.
+///
+/// Example) Verifying the order of a lookup table
+/// ```rust
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// static PRIMES: Seq<u32> = Seq::ConsRef(2, &Seq::ConsRef(3, &Seq::ConsRef(5, &Seq::Empty)));
+/// assert_seq_sorted!(PRIMES);
+/// # fn main() {}
+/// ```
+/// Unsorted elements fail the build
+/// ```compile_fail
+/// #[macro_use] extern crate seq;
+/// use seq::Seq;
+///
+/// static PRIMES: Seq<u32> = Seq::ConsRef(2, &Seq::ConsRef(5, &Seq::ConsRef(3, &Seq::Empty)));
+/// assert_seq_sorted!(PRIMES);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_seq_sorted {
+    ($seq:expr) => {
+        const _: () = {
+            let mut prev = None;
+            let mut cur = &$seq;
+            while let Some((sl, rt)) = cur.__split_node() {
+                let mut i = sl.len();
+                while i > 0 {
+                    i -= 1;
+                    let last = prev;
+                    prev = Some(&sl[i]);
+                    if let Some(last) = last {
+                        assert!(*last <= sl[i], "sequence is not sorted");
+                    }
+                }
+                cur = rt;
+            }
+        };
+    };
+}
+
+// the modules are declared following the macro definitions, making the macros available
+#[cfg(all(feature = "arbitrary", not(feature = "lite-seq")))]
+mod arbitrary;
+#[cfg(feature = "rkyv")]
+pub mod archive;
+#[cfg(feature = "arena")]
+pub mod arena;
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+pub mod branch;
+#[cfg(feature = "alloc")]
+pub mod cell;
+#[cfg(feature = "chunk")]
+pub mod chunk;
+#[cfg(feature = "std")]
+pub mod codec;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub mod de;
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+pub mod convert;
+#[cfg(feature = "alloc")]
+pub mod cursor;
+pub mod diff;
+pub mod display;
+#[cfg(feature = "alloc")]
+pub mod dot;
+pub mod env;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod hashed;
+#[cfg(feature = "arc")]
+pub mod history;
+pub mod inline;
+#[cfg(all(feature = "arena", feature = "std"))]
+pub mod interner;
+pub mod iter;
+pub mod layered;
+#[cfg(all(feature = "lazy", not(feature = "lite-seq")))]
+pub mod lazy;
+pub mod limits;
+#[cfg(feature = "log")]
+pub mod logging;
+pub mod map;
+pub mod path;
+pub mod pin;
+#[cfg(all(feature = "proptest", not(feature = "lite-seq")))]
+pub mod proptest;
+#[cfg(all(feature = "quickcheck", not(feature = "lite-seq")))]
+mod quickcheck;
+#[cfg(feature = "arena")]
+pub mod scope;
+#[cfg(feature = "serde")]
+pub mod ser;
+pub mod set;
+#[cfg(feature = "tracing")]
+pub mod trace;
+pub mod view;
+
+/// Holder of the static empty sequence per element type
+struct EmptySeq<'a, T: 'a>(PhantomData<&'a T>);
+
+impl<'a, T: 'a> EmptySeq<'a, T> {
+    const SEQ: &'a Seq<'a, T> = &Seq::Empty;
+}
+
 /// Function returns static reference to empty list
-pub fn empty<T>() -> &'static Seq<'static, T> { &Seq::Empty }
+pub const fn empty<T>() -> &'static Seq<'static, T> { EmptySeq::<T>::SEQ }
+
+/// Storage of the `ConsRef` nodes the `seqdef!` macro attaches the elements of an array as, not
+/// part of the public API. The nodes hold `Copy` elements, they need not be dropped.
+#[doc(hidden)]
+pub struct ConsRefNodes<'a, T: 'a, const N: usize> {
+    nodes: [MaybeUninit<Seq<'a, T>>; N],
+}
+
+impl<'a, T: Copy + 'a, const N: usize> ConsRefNodes<'a, T, N> {
+    /// Returns the storage without any node
+    pub const fn new() -> Self {
+        ConsRefNodes { nodes: [const { MaybeUninit::uninit() }; N] }
+    }
+
+    /// Attaches the items as `ConsRef` nodes onto `tail`, returning the top most node, the last
+    /// item being the head
+    pub fn link(&'a mut self, items: [T; N], tail: &'a Seq<'a, T>) -> Seq<'a, T> {
+        const { assert!(N > 0, "the array of elements must not be empty") };
+        let mut top = tail;
+        for (node, ft) in self.nodes.iter_mut().zip(&items[..N - 1]) {
+            top = node.write(Seq::ConsRef(*ft, top));
+        }
+        Seq::ConsRef(items[N - 1], top)
+    }
+}
+
+impl<'a, T: Copy + 'a, const N: usize> Default for ConsRefNodes<'a, T, N> {
+    fn default() -> Self {
+        ConsRefNodes::new()
+    }
+}
+
+/// Function attaches the items of `iter` as `ConsRef` elements, each one living in a stack frame
+/// of its own, and calls `f` with the sequence, the last item being the head
+pub fn collect_with<T, I, R, F>(iter: I, f: F) -> R
+    where I: IntoIterator<Item = T>, F: FnOnce(&Seq<'_, T>) -> R
+{
+    attach_with(&mut iter.into_iter(), &Seq::Empty, f)
+}
+
+/// Attaches the remaining items onto `seq` recursively, calling `f` in the innermost frame
+fn attach_with<T, I, R, F>(iter: &mut I, seq: &Seq<'_, T>, f: F) -> R
+    where I: Iterator<Item = T>, F: FnOnce(&Seq<'_, T>) -> R
+{
+    match iter.next() {
+        Some(ft) => attach_with(iter, &Seq::ConsRef(ft, seq), f),
+        None => f(seq),
+    }
+}
+
+/// Prints the elements root first, starting with the bottom element, writing `separator` between
+/// two of them. With feature `alloc` the element references are buffered; without, each one is
+/// found walking from the head, in O(n²). The sequence must not be cyclic.
+fn fmt_root_first<T, P>(seq: &Seq<'_, T>, separator: &str, f: &mut fmt::Formatter, print: P)
+                        -> fmt::Result
+    where P: Fn(&T, &mut fmt::Formatter) -> fmt::Result
+{
+    #[cfg(feature = "alloc")]
+    let elements = seq.into_iter().collect::<Vec<&T>>().into_iter().rev();
+    #[cfg(not(feature = "alloc"))]
+    let elements = (0..seq.len()).rev().filter_map(|i| seq.into_iter().nth(i));
+    for (i, ft) in elements.enumerate() {
+        if i > 0 {
+            f.write_str(separator)?;
+        }
+        print(ft, f)?;
+    }
+    Ok(())
+}
 
 /// By default a sequence is empty
 impl<'a, T> Default for Seq<'a, T> {
     fn default() -> Seq<'a, T> { Seq::Empty }
 }
 
+/// Links the slice as single `ConsSlice` node without copying; the last element of the slice
+/// becomes the head. `Seq::from_slice` clones the elements into owned nodes instead.
+impl<'a, T: 'a> From<&'a [T]> for Seq<'a, T> {
+    fn from(slice: &'a [T]) -> Seq<'a, T> {
+        Seq::ConsSlice(slice, EmptySeq::<'a, T>::SEQ)
+    }
+}
+
+/// Cloning a sequence shares the tails referenced by `ConsRef`, `ConsSlice` and `ConsArc` nodes
+/// with the clone, sharing the slices, too; only
+/// the head and the boxed `ConsOwn` elements are cloned, in a loop
+impl<'a, T: Clone> Clone for Seq<'a, T> {
+    fn clone(&self) -> Seq<'a, T> {
+        match *self {
+            Seq::Empty => Seq::Empty,
+            Seq::ConsRef(ref ft, rt) => Seq::ConsRef(ft.clone(), rt),
+            Seq::ConsSlice(sl, rt) => Seq::ConsSlice(sl, rt),
+            #[cfg(feature = "chunk")]
+            Seq::ConsChunk(ref ch, rt) => Seq::ConsChunk(ch.clone(), rt),
+            #[cfg(feature = "arc")]
+            Seq::ConsArc(ref ft, ref rt) => Seq::ConsArc(ft.clone(), rt.clone()),
+            #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+            Seq::ConsOwn(..) => {
+                let mut owned = Vec::new();
+                let mut cur = self;
+                while let Seq::ConsOwn(ref ft, ref rt) = *cur {
+                    owned.push(ft);
+                    cur = &**rt;
+                }
+                // rebuild the boxed elements bottom-up on top of the first non-boxed element
+                let mut seq = cur.clone();
+                for ft in owned.into_iter().rev() {
+                    seq = Seq::ConsOwn(ft.clone(), Box::new(seq));
+                }
+                seq
+            }
+        }
+    }
+}
+
+/// With feature `lite-seq` a sequence consists of the element and a reference only, it is copied
+/// if the element type is `Copy`; the owning variants of features `chunk` and `arc` prevent the
+/// copy
+#[cfg(all(feature = "lite-seq", not(feature = "chunk"), not(feature = "arc")))]
+impl<'a, T: Copy> Copy for Seq<'a, T> {}
+
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Takes the tail owned by the top most node, unless borrowed or shared
+    #[cfg(any(all(feature = "alloc", not(feature = "lite-seq")), feature = "arc"))]
+    fn unlink_tail(&mut self) -> Option<Seq<'a, T>> {
+        match *self {
+            #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+            Seq::ConsOwn(_, ref mut rt) => Some(mem::replace(&mut **rt, Seq::Empty)),
+            #[cfg(feature = "arc")]
+            Seq::ConsArc(_, ref mut rt) => Arc::get_mut(rt).map(|rt| mem::replace(rt, Seq::Empty)),
+            _ => None,
+        }
+    }
+}
+
+/// Dropping a sequence unlinks the owned tails one by one in a loop, so dropping long sequences
+/// of `ConsOwn` or `ConsArc` elements does not overflow the stack; a reference-counted tail is
+/// unlinked if not shared
+#[cfg(any(all(feature = "alloc", not(feature = "lite-seq")), feature = "arc"))]
+impl<'a, T: 'a> Drop for Seq<'a, T> {
+    fn drop(&mut self) {
+        let mut next = self.unlink_tail();
+        while let Some(mut node) = next {
+            // drops the node, its tail has been unlinked already
+            next = node.unlink_tail();
+        }
+    }
+}
+
 /// Two sequences of type T are equal in case of identical length and sequence of equal data elements.
+///
+/// The comparison stops as soon as both sides reach the same shared tail, so comparing two
+/// branches of a common tail only compares the elements not shared. A shared tail is equal to
+/// itself even if its elements are not, such as `f64::NAN`: for element types with a partial
+/// equivalence only, comparing sequences sharing a tail differs from comparing the elements.
 impl<'a, T: PartialEq> PartialEq for Seq<'a, T> {
     fn eq(&self, other: &Seq<'a, T>) -> bool {
-        match (self, other) {
-            (&Seq::Empty, &Seq::Empty) => true,
-            (&Seq::ConsRef(ref ft1, ref rt1), &Seq::ConsRef(ref ft2, ref rt2))
-            => ft1 == ft2 && rt1 == rt2,
-            #[cfg(not(feature = "lite-seq"))]
-            (&Seq::ConsRef(ref ft1, ref rt1), &Seq::ConsOwn(ref ft2, ref rt2))
-            => ft1 == ft2 && *rt1 == &**rt2,
-            #[cfg(not(feature = "lite-seq"))]
-            (&Seq::ConsOwn(ref ft1, ref rt1), &Seq::ConsRef(ref ft2, ref rt2))
-            => ft1 == ft2 && &**rt1 == *rt2,
-            #[cfg(not(feature = "lite-seq"))]
-            (&Seq::ConsOwn(ref ft1, ref rt1), &Seq::ConsOwn(ref ft2, ref rt2))
-            => ft1 == ft2 && rt1 == rt2,
-            _ => false,
+        let mut iter1 = self.into_iter();
+        let mut iter2 = other.into_iter();
+        loop {
+            if iter1.same_position(&iter2) {
+                return true;
+            }
+            match (iter1.next(), iter2.next()) {
+                (None, None) => return true,
+                (Some(ft1), Some(ft2)) if ft1 == ft2 => continue,
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// A sequence equals a slice of equal length and equal elements in iteration order, the head
+/// comparing to the first element of the slice.
+///
+/// Note, the conversions `From<Vec<T>>` and `to_vec` use the opposite order, the last element
+/// of the vector being the head; a vector converted into a sequence equals the reversed vector.
+impl<'a, T: PartialEq> PartialEq<[T]> for Seq<'a, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.into_iter().eq(other)
+    }
+}
+
+/// A sequence equals a slice of equal length and equal elements, see `PartialEq<[T]>`
+impl<'a, 'b, T: PartialEq> PartialEq<&'b [T]> for Seq<'a, T> {
+    fn eq(&self, other: &&'b [T]) -> bool {
+        *self == **other
+    }
+}
+
+/// A sequence equals a vector of equal length and equal elements in iteration order, the head
+/// comparing to the first element of the vector; other than the order of `From<Vec<T>>` and
+/// `to_vec`, see `PartialEq<[T]>`
+#[cfg(feature = "alloc")]
+impl<'a, T: PartialEq> PartialEq<Vec<T>> for Seq<'a, T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        *self == **other
+    }
+}
+
+/// Sequences of type T with full equivalence relation, may be used as keys of a `HashMap` or
+/// elements of a `HashSet`
+impl<'a, T: Eq> Eq for Seq<'a, T> {}
+
+/// Sequences are ordered lexicographically like slices, comparing element by element starting
+/// with the head; the comparison stops as soon as both sides reach the same shared tail. A
+/// shared tail compares `Equal` to itself, even if its elements are unordered, such as
+/// `f64::NAN`, which element-wise comparison would answer with `None`.
+impl<'a, T: PartialOrd> PartialOrd for Seq<'a, T> {
+    fn partial_cmp(&self, other: &Seq<'a, T>) -> Option<Ordering> {
+        cmp_elements(self, other, T::partial_cmp)
+    }
+}
+
+/// Sequences are ordered lexicographically like slices, see `PartialOrd`
+impl<'a, T: Ord> Ord for Seq<'a, T> {
+    fn cmp(&self, other: &Seq<'a, T>) -> Ordering {
+        // totally ordered elements always compare to Some
+        cmp_elements(self, other, |ft1, ft2| Some(ft1.cmp(ft2))).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Compares the elements of both sequences lexicographically by `cmp`, stopping at the first
+/// element not `Equal`, or as soon as both reach the same shared tail
+fn cmp_elements<T, F>(seq1: &Seq<'_, T>, seq2: &Seq<'_, T>, mut cmp: F) -> Option<Ordering>
+    where F: FnMut(&T, &T) -> Option<Ordering>
+{
+    let mut iter1 = seq1.into_iter();
+    let mut iter2 = seq2.into_iter();
+    loop {
+        if iter1.same_position(&iter2) {
+            return Some(Ordering::Equal);
+        }
+        match (iter1.next(), iter2.next()) {
+            (None, None) => return Some(Ordering::Equal),
+            (None, Some(_)) => return Some(Ordering::Less),
+            (Some(_), None) => return Some(Ordering::Greater),
+            (Some(ft1), Some(ft2)) => match cmp(ft1, ft2) {
+                Some(Ordering::Equal) => continue,
+                ord => return ord,
+            },
         }
     }
 }
 
-/// Debug format of a sequence prints the head element only
+/// A sequence hashes its elements in order followed by the number of elements, consistent with
+/// `PartialEq`
+impl<'a, T: Hash> Hash for Seq<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0;
+        for ft in self {
+            ft.hash(state);
+            len += 1;
+        }
+        state.write_usize(len);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T: Hash> Seq<'a, T> {
+    /// Returns the hash of the sequence, computed by `Hash` with `DefaultHasher::new()`.
+    ///
+    /// The hash depends on the elements only, sequences being equal have identical hashes,
+    /// irrespective of the kinds of nodes holding them. The hash is
+    /// deterministic, it is stable between runs of the same binary, and for the same toolchain
+    /// within a major version of this crate; hashes may be stored in caches accordingly.
+    /// Note, the algorithm of `DefaultHasher` may change between Rust releases.
+    pub fn canonical_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Debug format of a sequence prints the head element only; the alternate format `{:#?}`
+/// prints the elements of the sequence, at most [`MAX_DEBUG_ELEMENTS`], marking a cyclic
+/// sequence as such. The cycle is reported once the number of elements printed is a multiple of
+/// the cycle length, not less than the number of elements leading into the cycle; depending on
+/// the lengths, part of the cycle may not have been printed by then, but no element is printed
+/// twice.
+///
+/// [`MAX_DEBUG_ELEMENTS`]: display/constant.MAX_DEBUG_ELEMENTS.html
 impl<'a, T: fmt::Debug> fmt::Debug for Seq<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            &Seq::Empty => write!(f, "<>"),
-            &Seq::ConsRef(ref ft, _) => write!(f, "<{:?},...>", ft),
-            #[cfg(not(feature = "lite-seq"))]
-            &Seq::ConsOwn(ref ft, _) => write!(f, "<{:?},...>", ft),
+        if f.alternate() {
+            return self.fmt_elements(f);
+        }
+        match self.head() {
+            None => write!(f, "<>"),
+            Some(ft) => write!(f, "<{:?},...>", ft),
         }
     }
 }
 
+impl<'a, T: fmt::Debug> Seq<'a, T> {
+    /// Prints the elements as list, detecting cycles by a second cursor moving at double speed
+    fn fmt_elements(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut iter = self.into_iter();
+        let mut fast = Some(self.into_iter());
+        let mut count = 0;
+        while let Some(ft) = iter.next() {
+            if count == display::MAX_DEBUG_ELEMENTS {
+                list.entry(&format_args!("..."));
+                break;
+            }
+            list.entry(ft);
+            count += 1;
+            fast = fast.and_then(|mut fast| fast.next().and(fast.next()).map(|_| fast));
+            if fast.as_ref().is_some_and(|fast| fast.same_position(&iter)) {
+                list.entry(&format_args!("<cyclic>"));
+                break;
+            }
+        }
+        list.finish()
+    }
+}
+
 /// A sequence implements the `IntoIterator` trait
 ///
 /// # Example
@@ -271,13 +1389,54 @@ impl<'a, T: 'a> IntoIterator for &'a Seq<'a, T> {
     type IntoIter = SeqIterator<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        SeqIterator { cur: &self }
+        SeqIterator { cur: self, slice: &[] }
+    }
+}
+
+/// Iterating a boxed sequence reference, without explicit deref
+#[cfg(feature = "alloc")]
+impl<'a, 'b, T: 'a> IntoIterator for &'b Box<Seq<'a, T>> where 'a: 'b {
+    type Item = &'b T;
+    type IntoIter = SeqIterator<'b, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (**self).into_iter()
     }
 }
 
-/// The sequence iterator representation
+/// The sequence iterator representation; cloning saves the iteration position
+#[derive(Debug)]
 pub struct SeqIterator<'a, T: 'a> {
     cur: &'a Seq<'a, T>,
+    // the elements of the current node not yielded yet, preceding the sequence `cur`
+    slice: &'a [T],
+}
+
+/// Cloning the iterator copies the position only, the elements need not be `Clone`
+impl<'a, T: 'a> Clone for SeqIterator<'a, T> {
+    fn clone(&self) -> Self {
+        SeqIterator { cur: self.cur, slice: self.slice }
+    }
+}
+
+impl<'a, T: 'a> SeqIterator<'a, T> {
+    /// Moves on to the next node, returning false at the bottom of the sequence
+    fn next_node(&mut self) -> bool {
+        match self.cur.split_node() {
+            Some((sl, rt)) => {
+                self.slice = sl;
+                self.cur = rt;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns true, if both iterators yield the very same elements from now on
+    fn same_position(&self, other: &SeqIterator<'a, T>) -> bool {
+        ptr::eq(self.cur, other.cur)
+            && ((self.slice.is_empty() && other.slice.is_empty()) || ptr::eq(self.slice, other.slice))
+    }
 }
 
 /// The sequence iterator behavior implementation
@@ -285,28 +1444,85 @@ impl<'a, T: 'a> Iterator for SeqIterator<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.cur {
-            &Seq::Empty => Option::None,
-            &Seq::ConsRef(ref ft, ref rt) => {
-                self.cur = &*rt;
-                Option::Some(&*ft)
+        loop {
+            if let Some((ft, rest)) = self.slice.split_last() {
+                self.slice = rest;
+                return Option::Some(ft);
             }
-            #[cfg(not(feature = "lite-seq"))]
-            &Seq::ConsOwn(ref ft, ref rt) => {
-                self.cur = &**rt; // deref boxed rest
-                Option::Some(&*ft)
+            if !self.next_node() {
+                return Option::None;
+            }
+        }
+    }
+
+    /// The lower bound counts the elements of the current node, counting all elements would walk
+    /// the sequence; see `iter_with_len` for exact bounds
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.cur.split_node() {
+            Some((sl, _)) => (self.slice.len() + sl.len(), None),
+            None => (self.slice.len(), Some(self.slice.len())),
+        }
+    }
+
+    fn count(self) -> usize {
+        self.slice.len() + self.cur.len()
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        while n >= self.slice.len() {
+            n -= self.slice.len();
+            self.slice = &[];
+            if !self.next_node() {
+                return None;
+            }
+        }
+        let index = self.slice.len() - 1 - n;
+        let ft = &self.slice[index];
+        self.slice = &self.slice[..index];
+        Some(ft)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        let mut last = self.slice.first();
+        while self.next_node() {
+            last = self.slice.first().or(last);
+        }
+        last
+    }
+
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+        where F: FnMut(B, Self::Item) -> B
+    {
+        let mut acc = init;
+        loop {
+            acc = self.slice.iter().rev().fold(acc, &mut f);
+            if !self.next_node() {
+                return acc;
             }
         }
     }
 }
 
+/// Reaching the bottom of the sequence, the iterator keeps returning `None`
+impl<'a, T: 'a> FusedIterator for SeqIterator<'a, T> {}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    use std::boxed::Box;
+    use std::vec::Vec;
     use super::Seq;
-    #[cfg(not(feature = "lite-seq"))]
+    use std::cmp::Ordering;
+    use std::collections::{BTreeMap, HashSet};
+    use std::collections::hash_map::DefaultHasher;
+    use std::fmt;
+    use std::hash::{Hash, Hasher};
+    use std::ptr;
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
     use super::SeqIterator;
+    use super::OWNED_NODES;
     use super::empty;
-    #[cfg(not(feature = "lite-seq"))]
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
     use std::ops;
 
     struct MyData(&'static str);
@@ -317,39 +1533,441 @@ mod tests {
     static CYC_C : Seq<MyData> = Seq::ConsRef(MyData("And"), &CYC_B); // len()==3
     static CYC_D : Seq<MyData> = Seq::ConsRef(MyData("Round"), &CYC_C); // len()==5
 
+    // this static ring has 2 elements only
+    static RING_A: Seq<u32> = Seq::ConsRef(1, &RING_B);
+    static RING_B: Seq<u32> = Seq::ConsRef(2, &RING_A);
+
+    // this static table is verified at compile time
+    static TABLE: Seq<u32> = Seq::ConsRef(1, &Seq::ConsRef(2, &Seq::ConsRef(2, &Seq::Empty)));
+    assert_seq_len_eq!(TABLE, 3);
+    assert_seq_sorted!(TABLE);
+
+    // this static table links a slice, the head-first order is 1, 2, 3
+    static SLICED: Seq<u32> = Seq::ConsSlice(&[2, 1], &Seq::ConsRef(3, &Seq::Empty));
+    assert_seq_len_eq!(SLICED, 3);
+    assert_seq_sorted!(SLICED);
+
+    // this static ring links a slice, it has 3 elements only
+    static SLICED_RING_A: Seq<u32> = Seq::ConsSlice(&[1, 2], &SLICED_RING_B);
+    static SLICED_RING_B: Seq<u32> = Seq::ConsRef(3, &SLICED_RING_A);
+
+    #[test]
+    fn test_cyclic() {
+        // take first 12 elements from cyclic ring and count the characters
+        let sum = CYC_A.into_iter().take(3*4).fold(0, | x, y| x + y.0.len());
+        assert_eq!(3*20, sum);
+    }
+
+    #[test]
+    fn test_eq_shared_tail() {
+        // comparing branches of a cyclic ring terminates at the shared tail
+        let s = Seq::ConsRef(0u32, &RING_A);
+        let t = Seq::ConsRef(0u32, &RING_A);
+        assert_eq!(s, t);
+        assert_eq!(RING_A, RING_A);
+        assert_ne!(Seq::ConsRef(3u32, &RING_A), t);
+    }
+
+    #[test]
+    fn test_eq_shared_nan() {
+        // a shared tail equals itself, the NaN elements are not compared
+        let base = Seq::ConsRef(f64::NAN, empty());
+        let s = Seq::ConsRef(1.0, &base);
+        let t = Seq::ConsRef(1.0, &base);
+        assert_eq!(s, t);
+        let u = Seq::ConsRef(1.0, &Seq::ConsRef(f64::NAN, empty()));
+        assert_ne!(s, u);
+        assert_eq!(s.partial_cmp(&t), Some(Ordering::Equal));
+        assert_eq!(s.partial_cmp(&u), None);
+    }
+
+    #[test]
+    fn test_eq_slice() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        assert_eq!(s, [3, 2, 1][..]);
+        assert_eq!(s, &[3, 2, 1][..]);
+
+        #[cfg(feature = "alloc")]
+        {
+            assert_eq!(s, vec![3, 2, 1]);
+            assert_ne!(s, vec![1, 2, 3]);
+            assert_ne!(s, vec![3, 2]);
+            assert_eq!(*empty::<u32>(), Vec::new());
+        }
+    }
+
+    fn hash_of<T: Hash>(t: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        t.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_hash() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+        seqdef!(t; empty() => 0u32, 1, 2);
+        seqdef!(u; empty() => 0u32, 1);
+
+        assert_eq!(hash_of(&s), hash_of(&t));
+        assert_ne!(hash_of(&s), hash_of(&u));
+        assert_ne!(hash_of(&u), hash_of(empty::<u32>()));
+    }
+
+    #[cfg(all(feature = "std", not(feature = "lite-seq")))]
+    #[test]
+    fn test_canonical_hash() {
+        let s0: &Seq<u32> = empty();
+        let s1 = Seq::ConsRef(1u32, s0);
+        let refs = Seq::ConsRef(3u32, &Seq::ConsRef(2u32, &s1));
+        let owned = Seq::ConsOwn(3u32, Box::new(Seq::ConsOwn(2u32, Box::new(Seq::ConsOwn(1u32,
+            Box::new(Seq::Empty))))));
+        let mixed = Seq::ConsOwn(3u32, Box::new(Seq::ConsRef(2u32, &s1)));
+
+        assert_eq!(refs.canonical_hash(), owned.canonical_hash());
+        assert_eq!(refs.canonical_hash(), mixed.canonical_hash());
+        assert_eq!(hash_of(&refs), hash_of(&owned));
+        assert_eq!(hash_of(&refs), hash_of(&mixed));
+        assert_eq!(refs.canonical_hash(), hash_of(&refs));
+        assert_ne!(refs.canonical_hash(), s1.canonical_hash());
+    }
+
+    #[test]
+    fn test_hash_set() {
+        seqdef!(s; empty() => 0u32, 1, 2);
+        seqdef!(t; empty() => 0u32, 1, 2);
+        seqdef!(u; &s => 3);
+
+        let mut set = HashSet::new();
+        assert!(set.insert(&s));
+        assert!(!set.insert(&t));
+        assert!(set.insert(&u));
+        assert!(set.contains(&t));
+        assert_eq!(set.len(), 2);
+    }
+
+    // sequences are `Copy` with feature `lite-seq`, the clone is tested anyway
+    #[allow(clippy::clone_on_copy)]
+    #[test]
+    fn test_ord() {
+        seqdef!(a; empty() => 0u32, 1, 2);
+        seqdef!(b; empty() => 0u32, 1, 3);
+        seqdef!(c; empty() => 0u32, 1);
+        seqdef!(d; &a => 0);
+
+        // compared head first: <2,1,0> vs <3,1,0> vs <1,0> vs <0,2,1,0>
+        assert!(a < b);
+        assert!(c < a);
+        assert!(d < c);
+        assert!(a <= a.clone());
+        assert_eq!(RING_A.cmp(&RING_A), Ordering::Equal);
+
+        let mut sorted = vec![&b, &a, &d, &c];
+        sorted.sort();
+        assert_eq!(sorted, vec![&d, &c, &a, &b]);
+
+        let mut map = BTreeMap::new();
+        map.insert(&b, "b");
+        map.insert(&a, "a");
+        assert_eq!(map.values().cloned().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_consref() {
+        let s = Seq::ConsRef(1, &Seq::ConsRef(0, &Seq::Empty));
+        assert_ne!(&s, empty());
+    }
+
+    fn recurs(val: u32, max: u32, base: &Seq<u32>) {
+        let ext = Seq::ConsRef(val, base);
+
+        if val < max {
+            recurs(val + 1, max, &ext);
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let s0: &Seq<u32> = empty();
+        let s1 = Seq::ConsRef(1u32, s0);
+        assert_eq!(s0.len(), 0);
+        assert_eq!(s1.len(), 1);
+
+        assert_eq!(s0, empty());
+        assert_ne!(&s1, empty());
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let s0: &Seq<u32> = empty();
+        let s1 = Seq::ConsRef(1u32, s0);
+        let s2 = Seq::ConsSlice(&[], s0);
+
+        assert!(s0.is_empty());
+        assert!(!s1.is_empty());
+        assert!(s2.is_empty());
+    }
+
+    #[test]
+    fn test_try_push_owned() {
+        let pushed = Seq::Empty.try_push_owned(1u32)
+            .and_then(|s| s.try_push_owned(2));
+
+        if OWNED_NODES {
+            let pushed = pushed.unwrap();
+            assert_eq!(pushed, [2, 1][..]);
+            #[cfg(feature = "alloc")]
+            assert_eq!(Seq::try_from_vec(vec![1u32, 2]).ok(), Some(pushed));
+        } else {
+            assert_eq!(pushed.err().map(|(value, seq)| (value, seq.len())), Some((1, 0)));
+            #[cfg(feature = "alloc")]
+            assert_eq!(Seq::<u32>::try_from_vec(vec![1, 2]).err(), Some(vec![1, 2]));
+        }
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_cons_cow() {
+        use std::borrow::Cow;
+
+        fn push_even<'a>(value: u32, tail: Cow<'a, Seq<'a, u32>>) -> Seq<'a, u32> {
+            Seq::cons_cow(value - value % 2, tail)
+        }
+
+        let base = Seq::ConsRef(1u32, &Seq::Empty);
+        let borrowed = push_even(3, Cow::Borrowed(&base));
+        assert!(ptr::eq(borrowed.tail().unwrap(), &base));
+
+        let owned = push_even(5, Cow::Owned(Seq::ConsOwn(1, Box::new(Seq::Empty))));
+        assert_eq!(borrowed, [2, 1][..]);
+        assert_eq!(owned, [4, 1][..]);
+    }
+
+    #[test]
+    fn test_constructors() {
+        static BASE: Seq<u32> = Seq::cons(1, super::EmptySeq::<u32>::SEQ);
+        let s = Seq::cons_slice(&[3, 2], &BASE);
+        assert_eq!(s, [2, 3, 1][..]);
+        assert_eq!(s.split_node(), Some((&[3, 2][..], &BASE)));
+        assert_eq!(BASE.split_node(), Some((&[1][..], empty())));
+        assert_eq!(empty::<u32>().split_node(), None);
+
+        #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+        {
+            let owned = Seq::cons_own(4, Box::new(s.clone()));
+            assert_eq!(owned, [4, 2, 3, 1][..]);
+            assert_eq!(owned.split_node().map(|(sl, rt)| (sl, rt.len())), Some((&[4][..], 3)));
+        }
+    }
+
+    #[cfg(all(feature = "lite-seq", not(feature = "chunk"), not(feature = "arc")))]
+    #[test]
+    fn test_copy() {
+        fn depth<'a>(seq: Seq<'a, u32>, n: u32) -> usize {
+            if n == 0 {
+                return seq.len();
+            }
+            let pushed = Seq::ConsRef(n, &seq);
+            depth(pushed, n - 1) + depth(seq, 0)
+        }
+
+        let s = Seq::ConsRef(1u32, empty());
+        let t = s;
+        assert_eq!(s, t);
+        assert_eq!(depth(s, 3), 4 + 1 + 2 + 3);
+    }
+
+    #[test]
+    fn test_size_hint() {
+        seqdef!(s; empty() => 0u32, 1);
+
+        let mut iter = s.into_iter();
+        assert_eq!(iter.size_hint(), (1, None));
+        iter.next();
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(RING_A.into_iter().size_hint(), (1, None));
+    }
+
+    #[test]
+    fn test_iter_specialized() {
+        seqdef!(s; empty() => 0u32, 1, 2, 3);
+
+        assert_eq!(s.into_iter().count(), 4);
+        assert_eq!(s.into_iter().last(), Some(&0));
+        assert_eq!(s.into_iter().fold(0, |acc, x| acc * 10 + x), 3210);
+        assert_eq!(empty::<u32>().into_iter().last(), None);
+
+        let mut iter = s.into_iter();
+        assert_eq!(iter.nth(1), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.nth(1), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(RING_A.into_iter().nth(1001), Some(&2));
+    }
+
+    #[test]
+    fn test_iter_fused() {
+        fn assert_fused<I: ::std::iter::FusedIterator>(iter: I) -> I {
+            iter
+        }
+
+        seqdef!(s; empty() => 0u32);
+        let mut iter = assert_fused(s.into_iter());
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_iter_clone() {
+        seqdef!(s; empty() => MyData("a"), MyData("b"), MyData("c"));
+
+        let mut iter = s.into_iter();
+        iter.next();
+        let saved = iter.clone();
+        assert_eq!(iter.map(|d| d.0).collect::<Vec<_>>(), vec!["b", "a"]);
+        assert_eq!(saved.map(|d| d.0).collect::<Vec<_>>(), vec!["b", "a"]);
+
+        seqdef!(t; empty() => 1u32, 2);
+        assert_eq!(format!("{:?}", t.into_iter()), "SeqIterator { cur: <2,...>, slice: [] }");
+    }
+
     #[test]
-    fn test_cyclic() {
-        // take first 12 elements from cyclic ring and count the characters
-        let sum = CYC_A.into_iter().take(3*4).fold(0, | x, y| x + y.0.len());
-        assert_eq!(3*20, sum);
+    fn test_cons_slice() {
+        seqdef!(t; empty() => 0u32, 1, 2);
+        let data = [0u32, 1, 2];
+        let s = Seq::from(&data[..]);
+
+        assert_eq!(s, t);
+        assert_eq!(t, s);
+        assert_eq!(s.len(), 3);
+        assert_eq!(s.head(), Some(&2));
+        assert!(s.tail().unwrap().is_empty()); // the tail beneath the whole slice
+        assert_eq!(s.into_iter().cloned().collect::<Vec<_>>(), vec![2, 1, 0]);
+        assert_eq!(s.clone(), t);
+        #[cfg(feature = "std")]
+        assert_eq!(s.canonical_hash(), t.canonical_hash());
+        let lower_data = [0u32, 2];
+        let lower = Seq::from(&lower_data[..]);
+        assert!(lower < s);
+
+        let u = Seq::ConsRef(3u32, &s);
+        assert_eq!(u, [3, 2, 1, 0][..]);
+
+        let no_data: [u32; 0] = [];
+        let v = Seq::ConsSlice(&no_data, &u);
+        assert_eq!(v, u);
+        assert_eq!(v.head(), Some(&3));
+        let w = Seq::ConsSlice(&no_data, empty());
+        assert!(w.is_empty());
+        assert_eq!(&w, empty());
+        assert_eq!(format!("{:?}", w), "<>");
+
+        assert_eq!(SLICED, [1, 2, 3][..]);
     }
 
     #[test]
-    fn test_consref() {
-        let s = Seq::ConsRef(1, &Seq::ConsRef(0, &Seq::Empty));
-        assert_ne!(&s, empty());
+    fn test_cons_slice_iter() {
+        let data = [0u32, 1, 2];
+        let no_data: [u32; 0] = [];
+        let s0 = Seq::ConsSlice(&no_data, &TABLE);
+        let s = Seq::ConsSlice(&data, &s0);
+
+        assert_eq!(s.into_iter().size_hint(), (3, None));
+        assert_eq!(s.into_iter().count(), 6);
+        assert_eq!(s.into_iter().last(), Some(&2));
+        assert_eq!(s.into_iter().fold(0, |acc, x| acc * 10 + x), 210122);
+
+        let mut iter = s.into_iter();
+        assert_eq!(iter.nth(3), Some(&1));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.nth(1), None);
+
+        let mut iter = s.into_iter();
+        assert_eq!(iter.nth(1), Some(&1));
+        assert_eq!(iter.size_hint(), (1, None));
+
+        assert_eq!(format!("{:#?}", SLICED_RING_A), "[\n    2,\n    1,\n    3,\n    <cyclic>,\n]");
+        assert_eq!(SLICED_RING_A.into_iter().nth(299), Some(&3));
     }
 
-    fn recurs(val: u32, max: u32, base: &Seq<u32>) {
-        let ext = Seq::ConsRef(val, base);
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
 
-        if val < max {
-            recurs(val + 1, max, &ext);
+        assert_send_sync::<Seq<u32>>();
+        assert_send_sync::<Seq<&str>>();
+    }
+
+    #[cfg(feature = "arc")]
+    #[test]
+    fn test_cons_arc() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let shared = Arc::new(Seq::ConsArc(1u32, Arc::new(Seq::Empty)));
+        let s2 = Seq::ConsArc(2u32, shared.clone());
+        let s3 = Seq::ConsArc(3u32, shared.clone());
+        assert_eq!(s2, [2, 1][..]);
+        assert_eq!(s3, [3, 1][..]);
+        assert!(ptr::eq(s2.tail().unwrap(), s3.tail().unwrap()));
+        assert_eq!(s2.clone(), s2);
+        assert_eq!(Arc::strong_count(&shared), 3);
+
+        let snapshot = Arc::new(s3);
+        let worker = {
+            let snapshot = snapshot.clone();
+            thread::spawn(move || snapshot.into_iter().sum::<u32>())
+        };
+        assert_eq!(worker.join().unwrap(), 4);
+
+        drop(s2);
+        drop(snapshot);
+        assert_eq!(Arc::strong_count(&shared), 1);
+
+        let mut s = Arc::new(Seq::Empty);
+        for i in 0..200_000u32 {
+            s = Arc::new(Seq::ConsArc(i, s));
         }
+        assert_eq!(s.len(), 200_000);
+        let kept = s.clone();
+        drop(s);
+        assert_eq!(kept.head(), Some(&199_999));
     }
 
+    #[cfg(feature = "arc")]
     #[test]
-    fn test_empty() {
-        let s0: &Seq<u32> = empty();
-        let s1 = Seq::ConsRef(1u32, s0);
-        assert_eq!(s0.len(), 0);
-        assert_eq!(s1.len(), 1);
+    fn test_arc_snapshot_threads() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let history = Arc::new(Seq::ConsArc(2u32, Arc::new(Seq::ConsArc(1, Arc::new(Seq::Empty)))));
+        let workers: Vec<_> = (10..13u32)
+            .map(|i| {
+                // each worker owns a sequence of its own, sharing the history as tail
+                let snapshot: Seq<'static, u32> = Seq::ConsArc(i, history.clone());
+                thread::spawn(move || snapshot.into_iter().sum::<u32>())
+            })
+            .collect();
+        let sums: Vec<u32> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+        assert_eq!(sums, vec![13, 14, 15]);
+        assert_eq!(Arc::strong_count(&history), 1);
+    }
 
-        assert_eq!(s0, empty());
-        assert_ne!(&s1, empty());
+    #[test]
+    fn test_traversal_safe() {
+        seqdef!(s; empty() => 0, 1, 2);
+        s.assert_traversal_safe();
+    }
+
+    #[test]
+    fn test_traversal_safe_cyclic() {
+        // all operations are loop-based, any depth is safe
+        CYC_A.assert_traversal_safe();
     }
 
-    #[cfg(not(feature = "lite-seq"))]
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
     #[test]
     fn test_shared() {
         let s0: &Seq<u32> = empty();
@@ -381,8 +1999,14 @@ mod tests {
         // z-branch must not equal to t3 or t4
         assert_ne!(z, t3);
         assert_ne!(z, t4);
+
+        // a sequence must not equal to its own tail
+        assert_ne!(t4, t3);
+        assert_ne!(t3, t4);
     }
 
+    #[allow(clippy::match_ref_pats, clippy::assertions_on_constants, clippy::borrow_deref_ref,
+            clippy::needless_borrow, clippy::explicit_auto_deref)]
     #[test]
     fn test_match() {
         let s0: &Seq<u32> = empty();
@@ -401,11 +2025,9 @@ mod tests {
                     &Seq::ConsRef(h2, _) => {
                         assert_eq!(h2, 1u32);
                     }
-                     #[cfg(not(feature = "lite-seq"))]
                     _ => assert!(false, "seq was not owned!"),
                 }
             }
-            #[cfg(not(feature = "lite-seq"))]
             _ => assert!(false, "seq was not owned!"),
         }
 
@@ -420,12 +2042,45 @@ mod tests {
         println!("seq: {:?}, {:?}", s0, &s1);
     }
 
+    #[test]
+    fn test_printformat_alternate() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        assert_eq!(format!("{:?}", s), "<3,...>");
+        assert_eq!(format!("{:#?}", s), "[\n    3,\n    2,\n    1,\n]");
+        assert_eq!(format!("{:#?}", empty::<u32>()), "[]");
+        assert_eq!(format!("{:#?}", RING_A), "[\n    1,\n    2,\n    <cyclic>,\n]");
+    }
+
+    #[test]
+    fn test_printformat_alternate_lead_in() {
+        // one element leading into the ring of two, reported after two elements
+        static LEAD_IN: Seq<u32> = Seq::ConsRef(0, &RING_A);
+        assert_eq!(format!("{:#?}", LEAD_IN), "[\n    0,\n    1,\n    <cyclic>,\n]");
+
+        static LONG_LEAD_IN: Seq<u32> = Seq::ConsRef(5, &Seq::ConsRef(4, &LEAD_IN));
+        assert_eq!(format!("{:#?}", LONG_LEAD_IN).matches(',').count(), 5);
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_printformat_alternate_capped() {
+        let mut s: Seq<usize> = Seq::Empty;
+        let max = super::display::MAX_DEBUG_ELEMENTS;
+        for i in 0..max + 10 {
+            s = Seq::ConsOwn(i, Box::new(s));
+        }
+        let printed = format!("{:#?}", s);
+        assert_eq!(printed.lines().count(), max + 3);
+        assert!(printed.ends_with("    ...,\n]"));
+    }
+
     #[test]
     fn test_recursion() {
         recurs(0, 9, empty());
     }
 
-    #[cfg(not(feature = "lite-seq"))]
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
     fn prepend_boxed<'a>(start: u32, seq: &'a Seq<u32>) -> Box<Seq<'a, u32>> {
         Box::new(
             Seq::ConsOwn(
@@ -442,7 +2097,7 @@ mod tests {
                                         seq))))))))
     }
 
-    #[cfg(not(feature = "lite-seq"))]
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
     #[test]
     fn test_box() {
         let s0: &Seq<u32> = empty();
@@ -452,10 +2107,11 @@ mod tests {
         assert_ne!(&*s1, empty());
     }
 
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
     #[derive(PartialEq, PartialOrd, Debug)]
     struct Data([u32; 8]);
 
-    #[cfg(not(feature = "lite-seq"))]
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
     #[test]
     fn test_box_struct() {
         let s0: &Seq<Data> = empty();
@@ -470,7 +2126,89 @@ mod tests {
         assert_eq!(&s4, &s4);
     }
 
-    #[cfg(not(feature = "lite-seq"))]
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_drop_deep() {
+        let mut s: Seq<u32> = Seq::Empty;
+        for i in 0..200_000u32 {
+            s = Seq::ConsOwn(i, Box::new(s));
+        }
+        assert_eq!(s.head(), Some(&199_999));
+
+        let t = s.clone();
+        assert_eq!(s, t);
+    }
+
+    struct RootFirst<'a>(&'a Seq<'a, &'a str>);
+
+    impl<'a> fmt::Display for RootFirst<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            super::fmt_root_first(self.0, "/", f, fmt::Display::fmt)
+        }
+    }
+
+    #[test]
+    fn test_fmt_root_first() {
+        seqdef!(s; empty() => "a", "b", "c");
+        assert_eq!(format!("{}", RootFirst(&s)), "a/b/c");
+        assert_eq!(format!("{}", RootFirst(empty())), "");
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_fmt_root_first_deep() {
+        let mut s = Seq::Empty;
+        for _ in 0..1_000_000 {
+            s = Seq::ConsOwn("x", Box::new(s));
+        }
+        assert_eq!(format!("{}", RootFirst(&s)).len(), 2 * 1_000_000 - 1);
+    }
+
+    #[test]
+    fn test_empty_references() {
+        // references onto the empty sequence remain valid in static and const initializers,
+        // whether sequences implement Drop or not
+        static STATIC_EMPTY: &Seq<u32> = &Seq::Empty;
+        const CONST_EMPTY: &Seq<u32> = &Seq::Empty;
+        static LINKED: Seq<u32> = Seq::ConsRef(1, &Seq::Empty);
+        const fn first(ft: u32) -> Seq<'static, u32> {
+            Seq::ConsRef(ft, empty())
+        }
+        static CALLED: Seq<u32> = first(2);
+
+        assert!(STATIC_EMPTY.is_empty() && CONST_EMPTY.is_empty());
+        assert_eq!(LINKED.tail(), Some(CONST_EMPTY));
+        assert_eq!(CALLED.head(), Some(&2));
+    }
+
+    #[allow(clippy::clone_on_copy)]
+    #[test]
+    fn test_clone_shared() {
+        let s0: &Seq<u32> = empty();
+        let s1 = Seq::ConsRef(1u32, s0);
+        let s2 = Seq::ConsRef(2u32, &s1);
+        let c2 = s2.clone();
+
+        assert_eq!(c2, s2);
+        assert!(ptr::eq(c2.tail().unwrap(), &s1));
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_clone_boxed() {
+        let s0: &Seq<u32> = empty();
+        let s1 = Seq::ConsRef(1u32, s0);
+        let s3 = Seq::ConsOwn(3u32, Box::new(Seq::ConsOwn(2u32, Box::new(Seq::ConsRef(1u32, s0)))));
+        let b = prepend_boxed(10, &s1);
+        let c = b.clone();
+
+        assert_eq!(s3.clone(), s3);
+        assert_eq!(c, b);
+        assert!(ptr::eq(c.into_iter().nth(4).unwrap(), s1.head().unwrap()));
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[allow(clippy::unnecessary_fold)]
     #[test]
     fn test_iter() {
         let s0: &Seq<u32> = empty();
@@ -484,7 +2222,8 @@ mod tests {
         assert_eq!(sum, 10);
     }
 
-    #[cfg(not(feature = "lite-seq"))]
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[allow(clippy::unnecessary_fold)]
     #[test]
     fn test_iter_boxed() {
         let seq: Box<Seq<u32>> = prepend_boxed(1, empty());
@@ -492,6 +2231,12 @@ mod tests {
         let iter: SeqIterator<u32> = seq.into_iter();
         let sum = iter.fold(0, ops::Add::add);
         assert_eq!(sum, 10);
+
+        let mut sum = 0;
+        for ft in &seq {
+            sum += *ft;
+        }
+        assert_eq!(sum, 10);
     }
 
     #[test]
@@ -514,503 +2259,182 @@ mod tests {
         assert_eq!(&w, &u);
     }
 
-    #[test]
-    fn test_head_tail() {
-        let s: &Seq<u32> = empty();
-        let s = Seq::ConsRef(1u32, s);
-        let s = Seq::ConsRef(2u32, &s);
-        let s = Seq::ConsRef(3u32, &s);
-
-        let ft = s.head();
-        let rt = s.tail();
-
-        assert_eq!(ft.unwrap(), &3);
-        assert_eq!(rt.unwrap().head().unwrap(), &2);
-    }
-}
-
-
-#[cfg(all(feature = "benchmark", test))]
-mod benchmark {
-    extern crate test;
-
-    use super::Seq;
-    use super::empty;
-    use std::ops;
-    use std::vec;
-    use std::collections::LinkedList;
-
-    // Returns cumulation of  0, 0+1, 0+1+2, 0+1+2+3, ... 0+1+2+..+(N-1)
-    fn sum_of_sums(n: u32) -> u32
-    {
-        let cumulated = (n * (n + 1) * ((2 * n) + 1) / 6) + ((n * (n + 1)) / 2);
-        cumulated / 2
-    }
-
-    // Recursive function, adding an element and cumulate the sums, until N-1 is reached.
-    fn recurs_stack_list(l: &mut LinkedList<u32>, cnt: u32, n: u32) -> u32 {
-        if cnt < n {
-            l.push_back(cnt);
-            let sum = l.iter().fold(0u32, ops::Add::add);
-            let r = sum + recurs_stack_list(l, cnt + 1, n);
-            l.pop_back();
-            r
-        } else {
-            0
-        }
-    }
-
-    #[bench]
-    fn bench_list_008(b: &mut test::Bencher) {
-        const N: u32 = 8;
-
-        b.iter(|| {
-            let mut l = LinkedList::new();
-
-            let sum = recurs_stack_list(&mut l, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_list_016(b: &mut test::Bencher) {
-        const N: u32 = 16;
-
-        b.iter(|| {
-            let mut l = LinkedList::new();
-
-            let sum = recurs_stack_list(&mut l, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_list_032(b: &mut test::Bencher) {
-        const N: u32 = 32;
-
-        b.iter(|| {
-            let mut l = LinkedList::new();
-
-            let sum = recurs_stack_list(&mut l, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_list_064(b: &mut test::Bencher) {
-        const N: u32 = 64;
-
-        b.iter(|| {
-            let mut l = LinkedList::new();
-
-            let sum = recurs_stack_list(&mut l, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_list_128(b: &mut test::Bencher) {
-        const N: u32 = 128;
-
-        b.iter(|| {
-            let mut l = LinkedList::new();
-
-            let sum = recurs_stack_list(&mut l, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_list_256(b: &mut test::Bencher) {
-        const N: u32 = 256;
-
-        b.iter(|| {
-            let mut l = LinkedList::new();
-
-            let sum = recurs_stack_list(&mut l, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_list_512(b: &mut test::Bencher) {
-        const N: u32 = 512;
-
-        b.iter(|| {
-            let mut l = LinkedList::new();
-
-            let sum = recurs_stack_list(&mut l, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
+    static_seq! {
+        static STATIC_A: Seq<u32> = [0, 1, 2];
+        static STATIC_B: Seq<u32> = &STATIC_A => [3];
+        const CONST_C: Seq<&str> = ["main", "run",];
     }
+    assert_seq_len_eq!(STATIC_B, 4);
 
-    // Recursive function, adding an element and cumulate the sums, until N-1 is reached.
-    fn recurs_stack_vec(v: &mut vec::Vec<u32>, cnt: u32, n: u32) -> u32 {
-        if cnt < n {
-            v.push(cnt);
-            let sum = v.iter().fold(0u32, ops::Add::add);
-            let r = sum + recurs_stack_vec(v, cnt + 1, n);
-            v.truncate(cnt as usize);
-            r
-        } else {
-            0
-        }
+    #[test]
+    fn test_static_seq_macro() {
+        assert_eq!(STATIC_A, [2, 1, 0][..]);
+        assert!(ptr::eq(STATIC_B.tail().unwrap(), &STATIC_A));
+        assert_eq!(CONST_C.head(), Some(&"run"));
+        assert_eq!(CONST_C.len(), 2);
     }
 
-    #[bench]
-    fn bench_vec_008(b: &mut test::Bencher) {
-        const N: u32 = 8;
-
-        b.iter(|| {
-            let mut v = vec::Vec::new();
-            let sum = recurs_stack_vec(&mut v, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
+    #[cfg(feature = "macros")]
+    static_seqs! {
+        static GEN_A: Seq<u32> = [0, 1, 2, 3];
+        static GEN_B: Seq<u32> = [0, 1, 2, 4];
+        static GEN_RANGE: Seq<u32> = 0..=4095;
+        static GEN_FILE: Seq<u64> = include!("testdata/fib.txt");
+        static GEN_EMPTY: Seq<u32> = [];
+        static GEN_A_PREFIX: Seq<u32> = [0, 1, 2];
+        static GEN_SIGNED: Seq<i8> = -128..=127;
     }
 
-    #[bench]
-    fn bench_vec_016(b: &mut test::Bencher) {
-        const N: u32 = 16;
-
-        b.iter(|| {
-            let mut v = vec::Vec::new();
-            let sum = recurs_stack_vec(&mut v, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_static_seqs_macro() {
+        assert_eq!(GEN_A, [3, 2, 1, 0][..]);
+        assert_eq!(GEN_B.head(), Some(&4));
+        assert!(ptr::eq(GEN_A.tail().unwrap(), GEN_B.tail().unwrap()));
+        assert_eq!(GEN_RANGE.len(), 4096);
+        assert_eq!(GEN_RANGE.head(), Some(&4095));
+        assert_eq!(GEN_RANGE.into_iter().last(), Some(&0));
+        assert_eq!(GEN_FILE, [21, 13, 8, 5, 3, 2, 1, 1][..]);
+        assert!(GEN_EMPTY.is_empty());
+        assert!(ptr::eq(GEN_A.tail().unwrap(), &GEN_A_PREFIX));
+        assert!(ptr::eq(GEN_B.tail().unwrap(), &GEN_A_PREFIX));
+        assert_eq!(GEN_SIGNED.len(), 256);
+        assert_eq!(GEN_SIGNED.head(), Some(&127));
+        assert_eq!(GEN_SIGNED.into_iter().last(), Some(&-128));
     }
 
-    #[bench]
-    fn bench_vec_032(b: &mut test::Bencher) {
-        const N: u32 = 32;
-
-        b.iter(|| {
-            let mut v = vec::Vec::new();
-            let sum = recurs_stack_vec(&mut v, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
+    #[test]
+    fn test_seq_collect() {
+        let n = seq_collect!(0u32..100 => |s| {
+            assert!(matches!(*s, Seq::ConsRef(99, _)));
+            s.len()
         });
-    }
+        assert_eq!(n, 100);
 
-    #[bench]
-    fn bench_vec_064(b: &mut test::Bencher) {
-        const N: u32 = 64;
-
-        b.iter(|| {
-            let mut v = vec::Vec::new();
-            let sum = recurs_stack_vec(&mut v, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
+        assert!(super::collect_with(vec!["a", "b"], |s| *s == ["b", "a"][..]));
+        assert!(super::collect_with(None::<u8>, |s| s.is_empty()));
     }
 
-    #[bench]
-    fn bench_vec_128(b: &mut test::Bencher) {
-        const N: u32 = 128;
-
-        b.iter(|| {
-            let mut v = vec::Vec::new();
-            let sum = recurs_stack_vec(&mut v, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
+    #[cfg(not(feature = "arc"))]
+    #[test]
+    fn test_const_accessors() {
+        const DATA: [u32; 2] = [1, 2];
+        static S1: Seq<u32> = Seq::cons_slice(&[], empty());
+        static S2: Seq<u32> = Seq::cons_slice(&DATA, &S1);
+        static S3: Seq<u32> = Seq::cons(3, &S2);
+        const HEAD: Option<&u32> = S3.head();
+        const BENEATH: Option<&u32> = match S3.tail() {
+            Some(rt) => rt.head(),
+            None => None,
+        };
+        const EMPTY: bool = S1.is_empty();
+        const LEN: usize = S3.len();
+        assert_eq!(HEAD, Some(&3));
+        assert_eq!(BENEATH, Some(&2));
+        const _: () = assert!(EMPTY);
+        assert_eq!(LEN, 3);
+        assert!(empty::<u32>().is_empty());
     }
 
-    #[bench]
-    fn bench_vec_256(b: &mut test::Bencher) {
-        const N: u32 = 256;
-
-        b.iter(|| {
-            let mut v = vec::Vec::new();
-            let sum = recurs_stack_vec(&mut v, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
+    #[test]
+    fn test_with_pushed() {
+        seqdef!(s; 0u32, 1);
+        let (len, head) = s.with_pushed(2, |t| (t.len(), t.head().cloned()));
+        assert_eq!((len, head), (3, Some(2)));
+
+        let sum = s.with_pushed_all(2..5, |t| {
+            assert_eq!(*t, [4, 3, 2, 1, 0][..]);
+            t.with_pushed(5, |u| u.into_iter().sum::<u32>())
         });
+        assert_eq!(sum, 15);
+        assert!(s.with_pushed_all(None, |t| ptr::eq(t, &s)));
     }
 
-    #[bench]
-    fn bench_vec_512(b: &mut test::Bencher) {
-        const N: u32 = 512;
-
-        b.iter(|| {
-            let mut v = vec::Vec::new();
-            let sum = recurs_stack_vec(&mut v, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
+    #[test]
+    fn test_macro_forms() {
+        seqdef!(s; 0u32, 1, 2,);
+        seqdef!(t; empty() => 0u32, 1, 2,);
+        assert_eq!(s, t);
+        seqdef!(u; &s => 3,);
+        assert_eq!(u, [3, 2, 1, 0][..]);
+
+        seqdef!(r; repeat 5u32; 3);
+        assert_eq!(r, [5, 5, 5][..]);
+        seqdef!(h; 5u32, 5, 5);
+        assert_eq!(r.tail(), h.tail());
+        assert_eq!(r.tail().map(Seq::len), Some(2));
+        seqdef!(r; &s => repeat 4u32; 2,);
+        assert_eq!(r, [4, 4, 2, 1, 0][..]);
+        assert_eq!(r.tail().map(Seq::len), Some(4));
+
+        let data = [0u32, 1, 2];
+        seqdef!(v; from &data);
+        assert_eq!(v, s);
+        assert_eq!(v.tail(), s.tail());
+        assert_eq!(v.tail().and_then(Seq::tail), s.tail().and_then(Seq::tail));
+        assert_eq!(v.len(), s.len());
+        seqdef!(w; &v => from &data);
+        assert_eq!(w.len(), 6);
+        assert_eq!(w.tail().map(Seq::len), Some(5));
+
+        seqdef!(a; [0u8; 4]);
+        assert_eq!(a.len(), 1);
+        assert_eq!(a.head(), Some(&[0u8; 4]));
+        seqdef!(a; &a => [1u8; 4], [2u8; 4]);
+        assert_eq!(a.len(), 3);
+
+        seqdef!(x; rev 2u32, 1, 0,);
+        assert_eq!(x, s);
+        seqdef!(y; &s => rev 5, 4, 3);
+        assert_eq!(y, [5, 4, 3, 2, 1, 0][..]);
+        seqdef!(z; rev);
+        assert!(z.is_empty());
+        let _: &Seq<u32> = &z;
+        assert_eq!(w.head(), Some(&2));
     }
 
-    // Recursive function, adding an element and cumulate the sums, until N-1 is reached.
-    fn recurs_stack_seq(s: &Seq<u32>, cnt: u32, n: u32) -> u32 {
-        if cnt < n {
-            let ext_s = Seq::ConsRef(cnt, s);
-
-            let sum = ext_s.into_iter().fold(0u32, ops::Add::add);
-            sum + recurs_stack_seq(&ext_s, cnt + 1, n)
-        } else {
-            0
+    #[test]
+    fn test_seqref_macro() {
+        fn to_vec(seq: &Seq<u32>) -> Vec<u32> {
+            seq.into_iter().cloned().collect()
         }
-    }
-
-    #[bench]
-    fn bench_seq_008(b: &mut test::Bencher) {
-        const N: u32 = 8;
-
-        b.iter(|| {
-            let sum = recurs_stack_seq(empty(), 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_seq_016(b: &mut test::Bencher) {
-        const N: u32 = 16;
-
-        b.iter(|| {
-            let sum = recurs_stack_seq(empty(), 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_seq_032(b: &mut test::Bencher) {
-        const N: u32 = 32;
-
-        b.iter(|| {
-            let sum = recurs_stack_seq(empty(), 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_seq_064(b: &mut test::Bencher) {
-        const N: u32 = 64;
-
-        b.iter(|| {
-            let sum = recurs_stack_seq(empty(), 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_seq_128(b: &mut test::Bencher) {
-        const N: u32 = 128;
-
-        b.iter(|| {
-            let sum = recurs_stack_seq(empty(), 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-
-    #[bench]
-    fn bench_seq_256(b: &mut test::Bencher) {
-        const N: u32 = 256;
 
-        b.iter(|| {
-            let sum = recurs_stack_seq(empty(), 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_seq_512(b: &mut test::Bencher) {
-        const N: u32 = 512;
-
-        b.iter(|| {
-            let sum = recurs_stack_seq(empty(), 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
+        seqdef!(s; 0, 1);
+        assert_eq!(to_vec(seqref![0, 1, 2]), vec![2, 1, 0]);
+        assert_eq!(to_vec(seqref![&s => 2, 3,]), vec![3, 2, 1, 0]);
+        assert_eq!(to_vec(seqref![empty() => 7]), vec![7]);
+        assert!(to_vec(seqref![]).is_empty());
+        assert_eq!(seqref![0, 1], &s);
     }
 
-    // Recursive function, adding an element and cumulate the sums, until N-1 is reached.
-    fn recurs_stack_array(a: &mut [u32], cnt: u32, n: u32) -> u32 {
-        if cnt < n {
-            a[cnt as usize] = cnt;
-
-            let sum = a[..(cnt + 1) as usize].into_iter().fold(0u32, ops::Add::add);
-            sum + recurs_stack_array(a, cnt + 1, n)
-        } else {
-            0
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_seq_macro() {
+        struct Scopes {
+            names: Seq<'static, &'static str>,
         }
-    }
-
-    #[bench]
-    fn bench_array_008(b: &mut test::Bencher) {
-        const N: u32 = 8;
-        b.iter(|| {
-            let mut a: [u32; N as usize] = [0; N as usize];
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_array_016(b: &mut test::Bencher) {
-        const N: u32 = 16;
-        b.iter(|| {
-            let mut a: [u32; N as usize] = [0; N as usize];
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_array_032(b: &mut test::Bencher) {
-        const N: u32 = 32;
-        b.iter(|| {
-            let mut a: [u32; N as usize] = [0; N as usize];
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_array_064(b: &mut test::Bencher) {
-        const N: u32 = 64;
-        b.iter(|| {
-            let mut a: [u32; N as usize] = [0; N as usize];
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_array_128(b: &mut test::Bencher) {
-        const N: u32 = 128;
-        b.iter(|| {
-            let mut a: [u32; N as usize] = [0; N as usize];
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_array_256(b: &mut test::Bencher) {
-        const N: u32 = 256;
-        b.iter(|| {
-            let mut a: [u32; N as usize] = [0; N as usize];
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_array_512(b: &mut test::Bencher) {
-        const N: u32 = 512;
-        b.iter(|| {
-            let mut a: [u32; N as usize] = [0; N as usize];
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N - 1));
-            sum
-        });
-    }
-
-    #[bench]
-    fn bench_uninit_008(b: &mut test::Bencher) {
-        const N: u32 = 8;
-        b.iter(|| unsafe {
-            let mut a: [u32; N as usize] = ::std::mem::uninitialized();
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N-1));
-            sum
-        });
-    }
-
-
-    #[bench]
-    fn bench_uninit_016(b: &mut test::Bencher) {
-        const N: u32 = 16;
-        b.iter(|| unsafe {
-            let mut a: [u32; N as usize] = ::std::mem::uninitialized();
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N-1));
-            sum
-        });
-    }
-
-
-    #[bench]
-    fn bench_uninit_032(b: &mut test::Bencher) {
-        const N: u32 = 32;
-        b.iter(|| unsafe {
-            let mut a: [u32; N as usize] = ::std::mem::uninitialized();
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N-1));
-            sum
-        });
-    }
-
-
-    #[bench]
-    fn bench_uninit_064(b: &mut test::Bencher) {
-        const N: u32 = 64;
-        b.iter(|| unsafe {
-            let mut a: [u32; N as usize] = ::std::mem::uninitialized();
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N-1));
-            sum
-        });
-    }
 
+        seqdef!(s; "main", "run");
+        let scopes = Scopes { names: seq!["main", "run"] };
+        assert_eq!(scopes.names, s);
+        assert!(matches!(scopes.names, Seq::ConsOwn(..)));
 
-    #[bench]
-    fn bench_uninit_128(b: &mut test::Bencher) {
-        const N: u32 = 128;
-        b.iter(|| unsafe {
-            let mut a: [u32; N as usize] = ::std::mem::uninitialized();
-
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N-1));
-            sum
-        });
+        let e: Seq<u32> = seq![];
+        assert_eq!(&e, empty());
+        assert_eq!(seq![1, 2,], seq![1, 2]);
     }
 
+    #[test]
+    fn test_head_tail() {
+        let s: &Seq<u32> = empty();
+        let s = Seq::ConsRef(1u32, s);
+        let s = Seq::ConsRef(2u32, &s);
+        let s = Seq::ConsRef(3u32, &s);
 
-    #[bench]
-    fn bench_uninit_512(b: &mut test::Bencher) {
-        const N: u32 = 512;
-        b.iter(|| unsafe {
-            let mut a: [u32; N as usize] = ::std::mem::uninitialized();
+        let ft = s.head();
+        let rt = s.tail();
 
-            let sum = recurs_stack_array(&mut a, 0, N);
-            assert_eq!(sum, sum_of_sums(N-1));
-            sum
-        });
+        assert_eq!(ft.unwrap(), &3);
+        assert_eq!(rt.unwrap().head().unwrap(), &2);
     }
-
-
-
 }
@@ -0,0 +1,22 @@
+//! Stack-depth limits of the sequence internals.
+//!
+//! All operations on a [`Seq`] walk the sequence in a loop, none of them recurses once per
+//! element; dropping, cloning and comparing sequences of any length can not overflow the
+//! stack. Accordingly [`MAX_SAFE_DEPTH`] is unbounded, and [`Seq::assert_traversal_safe`]
+//! passes for sequences of any length, cyclic ones included.
+//!
+//! The formatting adapters printing root first, such as the `Display` format of a
+//! [`path::Path`], buffer the element references; without feature `alloc` they find each
+//! element walking from the head, in O(n²) time, but with constant stack depth as well.
+//!
+//! Safety-critical code should keep the check in its startup validation anyway, it documents
+//! the assumption and fails as soon as a build with recursive internals is linked.
+//!
+//! [`Seq`]: ../enum.Seq.html
+//! [`Seq::assert_traversal_safe`]: ../enum.Seq.html#method.assert_traversal_safe
+//! [`MAX_SAFE_DEPTH`]: constant.MAX_SAFE_DEPTH.html
+//! [`path::Path`]: ../path/struct.Path.html
+
+/// The maximum length of a sequence the recursive operations of this build are guaranteed to
+/// handle without stack-overflow. Equals `usize::MAX`, as all operations are loop-based.
+pub const MAX_SAFE_DEPTH: usize = usize::MAX;
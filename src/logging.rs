@@ -0,0 +1,88 @@
+//! Breadcrumbs of `log` records, enabled by feature `log`.
+//!
+//! The frames of a call stack push their scope names onto a sequence as they descend, each one
+//! borrowing the sequence of its caller. The function [`breadcrumb`] prints the scopes root
+//! first, and the macros [`seq_log!`], [`seq_info!`] and siblings prefix the message of a
+//! log record with the breadcrumb of the context sequence, for example
+//! `config > server > port: out of range`.
+//!
+//! # Example
+//! ```rust
+//! #[macro_use] extern crate seq;
+//! use seq::Seq;
+//!
+//! fn visit(ctx: &Seq<&str>, keys: &[&str]) {
+//!     match keys.split_first() {
+//!         Some((key, rest)) => visit(&Seq::ConsRef(*key, ctx), rest),
+//!         None => seq_info!(ctx, "reached leaf at depth {}", ctx.len()),
+//!     }
+//! }
+//! # fn main() { visit(seq::empty(), &["config", "server", "port"]); }
+//! ```
+//! [`breadcrumb`]: fn.breadcrumb.html
+//! [`seq_log!`]: ../macro.seq_log.html
+//! [`seq_info!`]: ../macro.seq_info.html
+
+use super::display::SeqDisplay;
+use super::Seq;
+
+/// The separator printed between two scopes of a breadcrumb
+pub const BREADCRUMB_SEPARATOR: &str = " > ";
+
+/// Returns the formatting adapter printing the scopes of `seq` root first, separated by
+/// [`BREADCRUMB_SEPARATOR`] unless configured otherwise, without brackets
+///
+/// [`BREADCRUMB_SEPARATOR`]: constant.BREADCRUMB_SEPARATOR.html
+pub fn breadcrumb<'a, T: 'a>(seq: &'a Seq<'a, T>) -> SeqDisplay<'a, T> {
+    seq.display().oldest_first().separator(BREADCRUMB_SEPARATOR).without_brackets()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::breadcrumb;
+    use super::super::{empty, Seq};
+    use std::string::{String, ToString};
+    use std::sync::Mutex;
+    use std::vec::Vec;
+
+    static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct Recorder;
+
+    impl log::Log for Recorder {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let line = format!("{} {}", record.level(), record.args());
+            RECORDS.lock().unwrap().push(line);
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_breadcrumb() {
+        seqdef!(s; empty() => "a", "b", "c");
+        assert_eq!(format!("{}", breadcrumb(&s)), "a > b > c");
+        assert_eq!(format!("{}", breadcrumb(&s).separator("::")), "a::b::c");
+        assert_eq!(format!("{}", breadcrumb(empty::<&str>())), "");
+    }
+
+
+    #[test]
+    fn test_macros() {
+        log::set_logger(&Recorder).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let ctx = Seq::ConsRef("outer", empty());
+        let ctx = Seq::ConsRef("inner", &ctx);
+        seq_info!(&ctx, "value {}", 1);
+        seq_warn!(empty::<&str>(), "no scope");
+        seq_log!(log::Level::Debug, &ctx, "done");
+        let records = RECORDS.lock().unwrap();
+        assert_eq!(*records, ["INFO outer > inner: value 1".to_string(),
+            "WARN no scope".to_string(), "DEBUG outer > inner: done".to_string()]);
+    }
+}
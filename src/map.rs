@@ -0,0 +1,221 @@
+//! Persistent association lists, mapping keys to values by a sequence of pairs.
+//!
+//! An [`AssocSeq`] holds the bindings in a `Seq<(K, V)>`, the innermost first. Inserting
+//! attaches a binding in front, borrowing the map beneath; a key bound again shadows its outer
+//! bindings, which reappear once the inner map goes out of scope. This is the environment of
+//! interpreters and the layering of configuration overrides, without copying and without heap
+//! allocation. Lookups walk the bindings, in O(n).
+//!
+//! # Example
+//! ```rust
+//! use seq::map::AssocSeq;
+//!
+//! let defaults = AssocSeq::new();
+//! let defaults = defaults.insert("color", "auto");
+//! let defaults = defaults.insert("pager", "less");
+//! {
+//!     let local = defaults.insert("color", "never");
+//!     assert_eq!(local.get("color"), Some(&"never"));
+//!     assert_eq!(local.get("pager"), Some(&"less"));
+//!     assert_eq!(local.since(&defaults).count(), 1);
+//! }
+//! assert_eq!(defaults.get("color"), Some(&"auto"));
+//! ```
+//! [`AssocSeq`]: struct.AssocSeq.html
+
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+use alloc::boxed::Box;
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::ptr;
+
+use super::{Seq, SeqIterator};
+
+/// A map of keys to values, the bindings being a sequence of pairs, innermost first
+pub struct AssocSeq<'a, K: 'a, V: 'a> {
+    seq: Seq<'a, (K, V)>,
+}
+
+/// AssocSeq method implementations
+impl<'a, K: 'a, V: 'a> AssocSeq<'a, K, V> {
+    /// Returns the map without any bindings
+    pub const fn new() -> Self {
+        AssocSeq { seq: Seq::Empty }
+    }
+
+    /// Returns the map of the bindings `seq`, the innermost first
+    pub const fn from_seq(seq: Seq<'a, (K, V)>) -> Self {
+        AssocSeq { seq }
+    }
+
+    /// Returns the sequence of bindings
+    pub fn as_seq(&self) -> &Seq<'a, (K, V)> {
+        &self.seq
+    }
+
+    /// Returns a new map binding `key` to `value` in front of this one, shadowing the outer
+    /// bindings of the key
+    pub fn insert(&'a self, key: K, value: V) -> AssocSeq<'a, K, V> {
+        AssocSeq { seq: Seq::ConsRef((key, value), &self.seq) }
+    }
+
+    /// Returns the value of the innermost binding of `key`
+    pub fn get<Q>(&'a self, key: &Q) -> Option<&'a V>
+        where K: Borrow<Q>, Q: Eq + ?Sized
+    {
+        self.seq.into_iter().find(|kv| kv.0.borrow() == key).map(|kv| &kv.1)
+    }
+
+    /// Returns true if `key` is bound
+    pub fn contains_key<Q>(&'a self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: Eq + ?Sized
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns all bindings, the innermost first, the shadowed ones included
+    pub fn bindings(&'a self) -> SeqIterator<'a, (K, V)> {
+        self.seq.into_iter()
+    }
+
+    /// Returns the visible bindings, the innermost first; a shadowed binding is skipped by
+    /// looking for its key among the bindings before, in O(n²) without allocation
+    pub fn iter(&'a self) -> Visible<'a, K, V> {
+        Visible { start: self.seq.into_iter(), iter: self.seq.into_iter(), pos: 0 }
+    }
+
+    /// Returns the bindings inserted in front of the map `outer`, the innermost first; all
+    /// bindings, if this map does not extend `outer`
+    pub fn since(&'a self, outer: &'a AssocSeq<'a, K, V>) -> Since<'a, K, V> {
+        Since { cur: &self.seq, slice: &[], outer: &outer.seq }
+    }
+}
+
+/// AssocSeq removal method implementations
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+impl<'a, K: Clone + 'a, V: Clone + 'a> AssocSeq<'a, K, V> {
+    /// Returns a new map without any binding of `key`. The bindings above the outermost binding
+    /// of the key are cloned into boxed nodes, the ones beneath are shared.
+    pub fn remove_owned<Q>(&'a self, key: &Q) -> AssocSeq<'a, K, V>
+        where K: Borrow<Q>, Q: Eq + ?Sized
+    {
+        let mut above = Vec::new();
+        let mut kept = 0;
+        let mut beneath = None;
+        let mut cur = &self.seq;
+        while let Some((sl1, rt1)) = cur.split_node() {
+            for (i, kv) in sl1.iter().enumerate().rev() {
+                above.push(kv);
+                if kv.0.borrow() == key {
+                    kept = above.len();
+                    beneath = Some((&sl1[..i], rt1));
+                }
+            }
+            cur = rt1;
+        }
+        let mut seq = match beneath {
+            Some((sl1, rt1)) => Seq::ConsSlice(sl1, rt1),
+            None => Seq::ConsSlice(&[], &self.seq),
+        };
+        for kv in above[..kept].iter().rev().filter(|kv| kv.0.borrow() != key) {
+            seq = Seq::ConsOwn((*kv).clone(), Box::new(seq));
+        }
+        AssocSeq { seq }
+    }
+}
+
+impl<'a, K: 'a, V: 'a> Default for AssocSeq<'a, K, V> {
+    fn default() -> Self {
+        AssocSeq::new()
+    }
+}
+
+/// The iterator over the visible bindings of a map
+pub struct Visible<'a, K: 'a, V: 'a> {
+    start: SeqIterator<'a, (K, V)>,
+    iter: SeqIterator<'a, (K, V)>,
+    pos: usize,
+}
+
+impl<'a, K: Eq + 'a, V: 'a> Iterator for Visible<'a, K, V> {
+    type Item = &'a (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let kv = self.iter.next()?;
+            let pos = self.pos;
+            self.pos += 1;
+            if !self.start.clone().take(pos).any(|prev| prev.0 == kv.0) {
+                return Some(kv);
+            }
+        }
+    }
+}
+
+/// The iterator over the bindings in front of an outer map
+pub struct Since<'a, K: 'a, V: 'a> {
+    cur: &'a Seq<'a, (K, V)>,
+    slice: &'a [(K, V)],
+    outer: &'a Seq<'a, (K, V)>,
+}
+
+impl<'a, K: 'a, V: 'a> Iterator for Since<'a, K, V> {
+    type Item = &'a (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((kv, rest)) = self.slice.split_last() {
+                self.slice = rest;
+                return Some(kv);
+            }
+            if ptr::eq(self.cur, self.outer) {
+                return None;
+            }
+            let (sl1, rt1) = self.cur.split_node()?;
+            self.slice = sl1;
+            self.cur = rt1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AssocSeq;
+
+    #[test]
+    fn test_insert_get() {
+        let m0 = AssocSeq::new();
+        let m1 = m0.insert("a", 1u32);
+        let m2 = m1.insert("b", 2);
+        let m3 = m2.insert("a", 3);
+        assert_eq!(m3.get("a"), Some(&3));
+        assert_eq!(m2.get("a"), Some(&1));
+        assert!(m3.contains_key("b"));
+        assert!(!m3.contains_key("c"));
+        assert_eq!(m3.bindings().count(), 3);
+        assert!(m3.iter().eq([("a", 3), ("b", 2)].iter()));
+        assert!(m3.since(&m1).eq([("a", 3), ("b", 2)].iter()));
+        assert_eq!(m1.since(&m3).count(), 1);
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_remove_owned() {
+        let m0 = AssocSeq::new();
+        let m1 = m0.insert("x", 0u32);
+        let m2 = m1.insert("a", 1);
+        let m3 = m2.insert("b", 2);
+        let m4 = m3.insert("a", 3);
+
+        let r = m4.remove_owned("a");
+        assert_eq!(r.get("a"), None);
+        assert!(r.bindings().eq([("b", 2), ("x", 0)].iter()));
+        // the bindings beneath the outermost binding of the key are shared
+        let beneath = r.as_seq().tail().unwrap();
+        assert!(core::ptr::eq(beneath.tail().unwrap(), m1.as_seq()));
+
+        let same = m4.remove_owned("z");
+        assert!(same.bindings().eq(m4.bindings()));
+    }
+}
@@ -0,0 +1,190 @@
+//! Breadcrumb paths of tree traversals, the segments held by a sequence.
+//!
+//! A [`Path`] holds the segments leaf first, as pushed by a recursive traversal descending the
+//! tree, each level borrowing the path of its parent. The `Display` format joins the segments
+//! root first, separated by [`DEFAULT_PATH_SEPARATOR`] unless configured by
+//! [`Path::with_separator`]; thereby `to_string` returns the path as `String`.
+//!
+//! # Example
+//! ```rust
+//! use seq::path::Path;
+//!
+//! let root = Path::new();
+//! let etc = root.push("etc");
+//! let conf = etc.push("hosts");
+//! assert_eq!(conf.to_string(), "etc/hosts");
+//! assert_eq!(conf.parent().unwrap().to_string(), "etc");
+//! assert!(conf.starts_with(&etc));
+//!
+//! let keys = Path::with_separator(".");
+//! let keys = keys.push("server");
+//! assert_eq!(keys.push("port").to_string(), "server.port");
+//! ```
+//! [`Path`]: struct.Path.html
+//! [`Path::with_separator`]: struct.Path.html#method.with_separator
+//! [`DEFAULT_PATH_SEPARATOR`]: constant.DEFAULT_PATH_SEPARATOR.html
+
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+use core::fmt;
+
+use super::{fmt_root_first, Seq, SeqIterator};
+
+/// The separator printed between two segments, unless configured otherwise
+pub const DEFAULT_PATH_SEPARATOR: &str = "/";
+
+/// A path of segments, the leaf first
+pub struct Path<'a, S: 'a> {
+    seq: Seq<'a, S>,
+    separator: &'a str,
+}
+
+/// Path method implementations
+impl<'a, S: 'a> Path<'a, S> {
+    /// Returns the root path, without any segment
+    pub const fn new() -> Self {
+        Path { seq: Seq::Empty, separator: DEFAULT_PATH_SEPARATOR }
+    }
+
+    /// Returns the root path, the segments of the paths pushed on it being separated by
+    /// `separator`
+    pub const fn with_separator(separator: &'a str) -> Self {
+        Path { seq: Seq::Empty, separator }
+    }
+
+    /// Returns the path of the segments `seq`, the leaf first
+    pub const fn from_seq(seq: Seq<'a, S>, separator: &'a str) -> Self {
+        Path { seq, separator }
+    }
+
+    /// Returns the sequence of segments, the leaf first
+    pub fn as_seq(&self) -> &Seq<'a, S> {
+        &self.seq
+    }
+
+    /// Returns the separator printed between two segments
+    pub fn separator(&self) -> &'a str {
+        self.separator
+    }
+
+    /// Returns a new path, descending into `segment` below this one
+    pub fn push(&'a self, segment: S) -> Path<'a, S> {
+        Path { seq: Seq::ConsRef(segment, &self.seq), separator: self.separator }
+    }
+
+    /// Returns the path without the leaf segment, unless the root
+    pub fn parent(&'a self) -> Option<Path<'a, S>> {
+        let mut cur = &self.seq;
+        while let Some((sl1, rt1)) = cur.split_node() {
+            if let Some((_, rest)) = sl1.split_last() {
+                return Some(Path { seq: Seq::ConsSlice(rest, rt1), separator: self.separator });
+            }
+            cur = rt1;
+        }
+        None
+    }
+
+    /// Returns the leaf segment, unless the root
+    pub fn leaf(&'a self) -> Option<&'a S> {
+        self.seq.head()
+    }
+
+    /// Returns the number of segments
+    pub fn depth(&'a self) -> usize {
+        self.seq.len()
+    }
+
+    /// Returns true if this is the root path
+    pub fn is_root(&'a self) -> bool {
+        self.seq.is_empty()
+    }
+
+    /// Returns the iterator over the segments, the leaf first
+    pub fn iter(&'a self) -> SeqIterator<'a, S> {
+        self.seq.into_iter()
+    }
+
+    /// Returns true if the segments of `prefix` are the leading segments of this path, counted
+    /// from the root
+    pub fn starts_with<'b>(&'a self, prefix: &'b Path<'b, S>) -> bool
+        where S: PartialEq
+    {
+        let (len, prefix_len) = (self.depth(), prefix.depth());
+        if prefix_len > len {
+            return false;
+        }
+        self.iter().skip(len - prefix_len).eq(prefix.iter())
+    }
+}
+
+/// Path conversion method implementations
+#[cfg(feature = "std")]
+impl<'a, S: AsRef<std::path::Path> + 'a> Path<'a, S> {
+    /// Returns the file system path of the segments, the root first
+    pub fn to_path_buf(&'a self) -> PathBuf {
+        let segments: Vec<&S> = self.iter().collect();
+        segments.into_iter().rev().collect()
+    }
+}
+
+impl<'a, S: 'a> Default for Path<'a, S> {
+    fn default() -> Self {
+        Path::new()
+    }
+}
+
+/// The segments are printed root first, separated by the separator of the path
+impl<'a, S: fmt::Display> fmt::Display for Path<'a, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_root_first(&self.seq, self.separator, f, |segment, f| fmt::Display::fmt(segment, f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Path;
+    use super::super::{empty, Seq};
+
+    #[test]
+    fn test_path() {
+        let root = Path::new();
+        let p1 = root.push("a");
+        let p2 = p1.push("b");
+        let p3 = p2.push("c");
+        assert_eq!(format!("{}", p3), "a/b/c");
+        assert_eq!(format!("{}", root), "");
+        assert_eq!(p3.depth(), 3);
+        assert_eq!(p3.leaf(), Some(&"c"));
+        assert!(p3.starts_with(&p2) && p3.starts_with(&root) && p3.starts_with(&p3));
+        assert!(!p1.starts_with(&p2));
+        assert!(root.parent().is_none());
+
+        let other = Path::with_separator("::");
+        let q1 = other.push("x");
+        let q2 = q1.push("b");
+        assert!(!q2.starts_with(&p1));
+        assert_eq!(format!("{}", q2), "x::b");
+    }
+
+    #[test]
+    fn test_parent_of_slice() {
+        let segments = ["usr", "local", "bin"];
+        let path = Path::from_seq(Seq::ConsSlice(&segments, empty()), "/");
+        let parent = path.parent().unwrap();
+        assert_eq!(format!("{}", path), "usr/local/bin");
+        assert_eq!(format!("{}", parent), "usr/local");
+        assert!(path.starts_with(&parent));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_path_buf() {
+        let root = Path::new();
+        let p1 = root.push("var");
+        let p2 = p1.push("log");
+        assert_eq!(p2.to_path_buf(), std::path::Path::new("var").join("log"));
+    }
+
+}
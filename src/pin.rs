@@ -0,0 +1,106 @@
+//! Pinned sequences, the nodes and inline elements of which keep their address.
+//!
+//! A [`PinSeq`] wraps a sequence node and is `!Unpin`: once pinned, by `core::pin::pin!` on
+//! the stack or by `Box::pin` on the heap, the node stays at its address until dropped. The head
+//! stored inline in a pinned node is handed out pinned, too, so its address may be registered
+//! with external systems, such as intrusive lists or interrupt handlers. Pinned nodes are
+//! extended by [`cons`], referencing the pinned tail.
+//!
+//! # Example
+//! ```rust
+//! use std::pin::pin;
+//! use seq::pin::PinSeq;
+//! use seq::Seq;
+//!
+//! let base = pin!(PinSeq::new(Seq::ConsRef(1u32, seq::empty())));
+//! let ext = pin!(base.as_ref().cons(2));
+//! let addr: *const u32 = &*ext.as_ref().pinned_head().unwrap();
+//! assert_eq!(unsafe { *addr }, 2);
+//! assert_eq!(ext.as_seq(), &[2, 1][..]);
+//! ```
+//! [`PinSeq`]: struct.PinSeq.html
+//! [`cons`]: struct.PinSeq.html#method.cons
+
+use core::marker::PhantomPinned;
+use core::ops::Deref;
+use core::pin::Pin;
+
+use super::Seq;
+
+/// A sequence node which is not moved once pinned
+pub struct PinSeq<'a, T: 'a> {
+    seq: Seq<'a, T>,
+    _pinned: PhantomPinned,
+}
+
+/// PinSeq method implementations
+impl<'a, T: 'a> PinSeq<'a, T> {
+    /// Returns the unpinned node wrapping `seq`, to be pinned before its address is relied upon
+    pub const fn new(seq: Seq<'a, T>) -> Self {
+        PinSeq { seq, _pinned: PhantomPinned }
+    }
+
+    /// Returns the sequence
+    pub fn as_seq(&self) -> &Seq<'a, T> {
+        &self.seq
+    }
+
+    /// Returns the pinned head, if stored inline in this node; the head of a `ConsSlice` node is
+    /// borrowed from the slice and not pinned by the node, it and the empty sequence yield `None`
+    pub fn pinned_head(self: Pin<&Self>) -> Option<Pin<&T>> {
+        let head = match self.get_ref().seq {
+            Seq::ConsRef(ref ft1, _) => Some(ft1),
+            #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+            Seq::ConsOwn(ref ft1, _) => Some(ft1),
+            #[cfg(feature = "chunk")]
+            Seq::ConsChunk(ref ch1, _) => ch1.as_slice().last(),
+            #[cfg(feature = "arc")]
+            Seq::ConsArc(ref ft1, _) => Some(ft1),
+            _ => None,
+        };
+        // the node is pinned and never handed out mutably, elements inline stay in place
+        head.map(|ft1| unsafe { Pin::new_unchecked(ft1) })
+    }
+
+    /// Returns the address of the pinned node, stable until the node is dropped
+    pub fn node_addr(self: Pin<&Self>) -> *const Seq<'a, T> {
+        &self.get_ref().seq
+    }
+
+    /// Returns a new node attaching `value` to this pinned node as tail
+    pub fn cons<'p>(self: Pin<&'p Self>, value: T) -> PinSeq<'p, T> {
+        PinSeq::new(Seq::ConsRef(value, &self.get_ref().seq))
+    }
+}
+
+/// Shared access to the sequence, the node can not be moved through it
+impl<'a, T: 'a> Deref for PinSeq<'a, T> {
+    type Target = Seq<'a, T>;
+
+    fn deref(&self) -> &Seq<'a, T> {
+        &self.seq
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{empty, Seq};
+    use super::PinSeq;
+    use core::pin::pin;
+
+    #[test]
+    fn test_pin_seq() {
+        let base = pin!(PinSeq::new(Seq::ConsRef(1u32, empty())));
+        let head_addr: *const u32 = &*base.as_ref().pinned_head().unwrap();
+        let ext = pin!(base.as_ref().cons(2));
+        assert_eq!(*ext.as_seq(), [2, 1][..]);
+        // the tail of the extension is the pinned node itself
+        assert!(core::ptr::eq(ext.tail().unwrap(), base.as_ref().node_addr()));
+        assert!(core::ptr::eq(ext.tail().unwrap().head().unwrap(), head_addr));
+
+        let data = [3u32];
+        let sliced = pin!(PinSeq::new(Seq::ConsSlice(&data, empty())));
+        assert!(sliced.as_ref().pinned_head().is_none());
+        assert_eq!(sliced.head(), Some(&3));
+    }
+}
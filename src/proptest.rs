@@ -0,0 +1,118 @@
+//! Strategies generating sequences for property-based testing with proptest, enabled by
+//! feature `proptest`.
+//!
+//! [`seq_of`] generates sequences of boxed `ConsOwn` elements, shrinking like vectors do:
+//! dropping elements and shrinking the elements themselves. [`shared_tail_pair_of`] generates
+//! two sequences sharing a common tail, exercising the code depending on shared nodes.
+//!
+//! # Example
+//! ```rust
+//! use seq::proptest::seq_of;
+//! # extern crate proptest;
+//! use proptest::strategy::{Strategy, ValueTree};
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::default();
+//! let s = seq_of(0u32..10, 1..4).new_tree(&mut runner).unwrap().current();
+//! assert!((1..4).contains(&s.len()));
+//! ```
+//! [`seq_of`]: fn.seq_of.html
+//! [`shared_tail_pair_of`]: fn.shared_tail_pair_of.html
+
+extern crate proptest;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+
+use self::proptest::collection::{vec, SizeRange};
+use self::proptest::strategy::Strategy;
+use super::Seq;
+
+/// Returns a strategy generating sequences of boxed elements, their length in range `size`;
+/// the sequences shrink by dropping elements and by shrinking the elements
+pub fn seq_of<S>(element: S, size: impl Into<SizeRange>)
+    -> impl Strategy<Value = Seq<'static, S::Value>>
+    where S: Strategy, S::Value: fmt::Debug + 'static
+{
+    vec(element, size).prop_map(Seq::from)
+}
+
+/// Returns a strategy generating two sequences referencing the same tail by `ConsRef`, the
+/// tail's length and the lengths of the two distinct prefixes being in range `size`. Each
+/// prefix holds one element at least. The shared tail is leaked, living until the end of the
+/// process.
+pub fn shared_tail_pair_of<S>(element: S, size: impl Into<SizeRange>)
+    -> impl Strategy<Value = (Seq<'static, S::Value>, Seq<'static, S::Value>)>
+    where S: Strategy + Clone, S::Value: fmt::Debug + 'static
+{
+    let size = size.into();
+    let prefix = SizeRange::new(size.start().max(1)..=size.end_incl().max(1));
+    (vec(element.clone(), size), vec(element.clone(), prefix.clone()), vec(element, prefix))
+        .prop_map(|(tail, p1, p2)| {
+            let shared: &'static Seq<'static, S::Value> = Box::leak(Box::new(Seq::from(tail)));
+            (attach(p1, shared), attach(p2, shared))
+        })
+}
+
+/// Attaches the elements onto the shared tail, the last element becoming the head; the bottom
+/// element of the prefix references the tail
+fn attach<T>(prefix: Vec<T>, shared: &'static Seq<'static, T>) -> Seq<'static, T> {
+    let mut seq = Seq::Empty;
+    for (i, ft) in prefix.into_iter().enumerate() {
+        seq = match i {
+            0 => Seq::ConsRef(ft, shared),
+            _ => Seq::ConsOwn(ft, Box::new(seq)),
+        };
+    }
+    seq
+}
+
+#[cfg(test)]
+mod tests {
+    use super::proptest::strategy::{Strategy, ValueTree};
+    use super::proptest::test_runner::{TestCaseError, TestError, TestRunner};
+    use super::super::Seq;
+    use super::{seq_of, shared_tail_pair_of};
+
+    #[test]
+    fn test_seq_of() {
+        let mut runner = TestRunner::deterministic();
+        let result = runner.run(&seq_of(0u32..100, 0..16), |s| {
+            assert!(s.len() < 16);
+            assert_eq!(s.len(), s.into_iter().count());
+            Ok(())
+        });
+        assert!(result.is_ok());
+
+        // a failing property shrinks to the shortest failing sequence
+        let result = TestRunner::deterministic().run(&seq_of(0u32..100, 0..16), |s| {
+            if s.len() < 3 { Ok(()) } else { Err(TestCaseError::fail("too long")) }
+        });
+        match result {
+            Err(TestError::Fail(_, s)) => assert_eq!(s, [0, 0, 0][..]),
+            _ => panic!("property expected to fail"),
+        }
+    }
+
+    #[test]
+    fn test_shared_tail_pair_of() {
+        fn bottom_prefix_node<'b>(seq: &'b Seq<'static, u8>) -> &'b Seq<'static, u8> {
+            let mut cur = seq;
+            while let Seq::ConsOwn(_, ref rt) = *cur {
+                cur = rt;
+            }
+            cur
+        }
+
+        let mut runner = TestRunner::deterministic();
+        let strategy = shared_tail_pair_of(0u8..10, 0..4);
+        for _ in 0..16 {
+            let (s1, s2) = strategy.new_tree(&mut runner).unwrap().current();
+            let (b1, b2) = (bottom_prefix_node(&s1), bottom_prefix_node(&s2));
+            assert!(matches!(*b1, Seq::ConsRef(..)));
+            // both sequences reference the same tail
+            assert!(core::ptr::eq(b1.tail().unwrap(), b2.tail().unwrap()));
+        }
+    }
+}
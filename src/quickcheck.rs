@@ -0,0 +1,60 @@
+//! Random sequences for property-based testing with quickcheck, enabled by feature
+//! `quickcheck`.
+//!
+//! An arbitrary sequence is made of boxed `ConsOwn` elements, its length being bounded by the
+//! size of the generator. Shrinking drops heads, trying the shortest tails first, and keeps
+//! the elements beneath unchanged.
+
+extern crate quickcheck;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use self::quickcheck::{Arbitrary, Gen};
+use super::Seq;
+
+/// Random sequences of boxed elements, shrinking by dropping heads
+impl<T: Arbitrary> Arbitrary for Seq<'static, T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Seq::from(Vec::<T>::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let vec = self.to_vec();
+        let len = vec.len();
+        // the number of heads dropped is halved step by step, down to a single one
+        let dropped = core::iter::successors(Some(len), |n| Some(n / 2)).take_while(|n| *n > 0);
+        Box::new(dropped.map(move |n| vec[..len - n].iter().cloned().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::quickcheck::{Arbitrary, Gen, QuickCheck};
+    use super::super::Seq;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_arbitrary() {
+        let mut g = Gen::new(16);
+        for _ in 0..32 {
+            let s = Seq::<u8>::arbitrary(&mut g);
+            assert!(s.len() <= 16);
+            assert!(s.is_empty() || matches!(s, Seq::ConsOwn(..)));
+        }
+    }
+
+    #[test]
+    fn test_shrink() {
+        let s: Seq<u32> = (0..8).collect();
+        let tails: Vec<Vec<u32>> = s.shrink().map(|t| t.to_vec()).collect();
+        assert_eq!(tails, vec![vec![], vec![0, 1, 2, 3], vec![0, 1, 2, 3, 4, 5],
+                               vec![0, 1, 2, 3, 4, 5, 6]]);
+        assert_eq!(Seq::<u32>::Empty.shrink().count(), 0);
+
+        fn prop_len(s: Seq<'static, u32>) -> bool {
+            s.len() == s.into_iter().count()
+        }
+        QuickCheck::new().quickcheck(prop_len as fn(Seq<'static, u32>) -> bool);
+    }
+}
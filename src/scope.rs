@@ -0,0 +1,170 @@
+//! Branded scopes, storing sequences in structs across call boundaries; enabled by feature
+//! `arena`.
+//!
+//! The function [`scope`] calls a closure with a fresh [`SeqScope`] token, its lifetime
+//! `'brand` being unique to the call. Sequences built against the token are [`BrandedSeq`]
+//! handles, their nodes allocated in an arena living until the closure returns. The handles
+//! are `Copy` and may be stored in structs parameterized by the brand, threaded through layers
+//! of a framework, and extended by any of those layers; no nested closure per extension is
+//! needed. The brand is invariant, so handles of distinct scopes can not be mixed. As with
+//! [`SeqArena`], the nodes are freed at once, without being dropped.
+//!
+//! # Example
+//! ```rust
+//! use seq::scope::{scope, BrandedSeq, SeqScope};
+//!
+//! struct Context<'brand> {
+//!     scope: SeqScope<'brand>,
+//!     path: BrandedSeq<'brand, &'static str>,
+//! }
+//!
+//! fn enter<'brand>(ctx: &Context<'brand>, name: &'static str) -> Context<'brand> {
+//!     Context { scope: ctx.scope, path: ctx.scope.cons(name, ctx.path) }
+//! }
+//!
+//! let depth = scope(|scope| {
+//!     let root = Context { scope, path: scope.empty() };
+//!     let inner = enter(&enter(&root, "server"), "handler");
+//!     inner.path.len()
+//! });
+//! assert_eq!(depth, 2);
+//! ```
+//! Handles of distinct scopes are rejected
+//! ```compile_fail
+//! use seq::scope::scope;
+//!
+//! scope(|outer| {
+//!     let s = outer.cons(1, outer.empty());
+//!     scope(|inner| {
+//!         inner.cons(2, s);
+//!     });
+//! });
+//! ```
+//! [`scope`]: fn.scope.html
+//! [`SeqScope`]: struct.SeqScope.html
+//! [`BrandedSeq`]: struct.BrandedSeq.html
+//! [`SeqArena`]: ../arena/struct.SeqArena.html
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use super::arena::SeqArena;
+use super::{EmptySeq, Seq};
+
+/// The invariant marker of the brand
+type Brand<'brand> = PhantomData<fn(&'brand ()) -> &'brand ()>;
+
+/// The token of a scope, allocating the nodes of branded sequences
+#[derive(Clone, Copy)]
+pub struct SeqScope<'brand> {
+    arena: &'brand SeqArena,
+    brand: Brand<'brand>,
+}
+
+/// A sequence built against the scope of the same brand, valid until the scope ends
+pub struct BrandedSeq<'brand, T: 'brand> {
+    seq: &'brand Seq<'brand, T>,
+    brand: Brand<'brand>,
+}
+
+/// Calls `f` with a fresh scope token; the nodes allocated by the scope are freed on return
+pub fn scope<R, F>(f: F) -> R
+    where F: for<'brand> FnOnce(SeqScope<'brand>) -> R
+{
+    let arena = SeqArena::new();
+    f(SeqScope { arena: &arena, brand: PhantomData })
+}
+
+/// SeqScope method implementations
+impl<'brand> SeqScope<'brand> {
+    /// Returns the empty sequence of this scope
+    pub fn empty<T: 'brand>(self) -> BrandedSeq<'brand, T> {
+        self.brand(EmptySeq::SEQ)
+    }
+
+    /// Returns a new sequence attaching `head` to `tail`, the node allocated by the scope
+    pub fn cons<T: 'brand>(self, head: T, tail: BrandedSeq<'brand, T>) -> BrandedSeq<'brand, T> {
+        self.brand(self.arena.cons(head, tail.seq))
+    }
+
+    /// Attaches the items of `iter` to `tail` one node per item, the last item being the head
+    pub fn extend<T: 'brand, I>(self, tail: BrandedSeq<'brand, T>, iter: I) -> BrandedSeq<'brand, T>
+        where I: IntoIterator<Item = T>
+    {
+        self.brand(self.arena.extend(tail.seq, iter))
+    }
+
+    fn brand<T: 'brand>(self, seq: &'brand Seq<'brand, T>) -> BrandedSeq<'brand, T> {
+        BrandedSeq { seq, brand: PhantomData }
+    }
+}
+
+/// BrandedSeq method implementations
+impl<'brand, T: 'brand> BrandedSeq<'brand, T> {
+    /// Returns the sequence, valid until the scope ends
+    pub fn as_seq(self) -> &'brand Seq<'brand, T> {
+        self.seq
+    }
+
+    /// Returns the tail as handle of the same scope, unless the sequence is empty
+    pub fn tail(self) -> Option<BrandedSeq<'brand, T>> {
+        self.seq.tail().map(|rt| BrandedSeq { seq: rt, brand: PhantomData })
+    }
+}
+
+impl<'brand, T: 'brand> Clone for BrandedSeq<'brand, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'brand, T: 'brand> Copy for BrandedSeq<'brand, T> {}
+
+/// Shared access to the sequence
+impl<'brand, T: 'brand> Deref for BrandedSeq<'brand, T> {
+    type Target = Seq<'brand, T>;
+
+    fn deref(&self) -> &Seq<'brand, T> {
+        self.seq
+    }
+}
+
+/// Formats the sequence
+impl<'brand, T: fmt::Debug + 'brand> fmt::Debug for BrandedSeq<'brand, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.seq, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scope, BrandedSeq, SeqScope};
+
+    struct Layer<'brand> {
+        scope: SeqScope<'brand>,
+        frames: BrandedSeq<'brand, u32>,
+    }
+
+    impl<'brand> Layer<'brand> {
+        fn push(&mut self, frame: u32) {
+            self.frames = self.scope.cons(frame, self.frames);
+        }
+    }
+
+    #[test]
+    fn test_scope() {
+        let (len, sum) = scope(|scope| {
+            let mut layer = Layer { scope, frames: scope.empty() };
+            for i in 0..10 {
+                layer.push(i);
+            }
+            let shared = layer.frames.tail().unwrap();
+            let other = scope.extend(shared, 20..22);
+            assert!(core::ptr::eq(other.tail().unwrap().tail().unwrap().as_seq(), shared.as_seq()));
+            (layer.frames.len(), other.into_iter().sum::<u32>())
+        });
+        assert_eq!(len, 10);
+        assert_eq!(sum, 36 + 41);
+    }
+}
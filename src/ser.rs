@@ -0,0 +1,109 @@
+//! Serialization of sequences with serde, enabled by feature `serde`.
+//!
+//! A sequence is serialized as list of its elements, starting with the head. The adapter
+//! [`OldestFirst`] serializes the elements in chronological order instead, starting with the
+//! bottom element; for struct fields the function [`serialize_oldest_first`] does the same
+//! with `#[serde(serialize_with = "...")]`.
+//!
+//! The length is written ahead of the elements, as required by some formats; the sequence
+//! must not be cyclic.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//!
+//! let s1 = Seq::ConsRef("main", seq::empty());
+//! let s2 = Seq::ConsRef("run", &s1);
+//! assert_eq!(serde_json::to_string(&s2).unwrap(), r#"["run","main"]"#);
+//! ```
+//! [`OldestFirst`]: struct.OldestFirst.html
+//! [`serialize_oldest_first`]: fn.serialize_oldest_first.html
+
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use super::Seq;
+
+/// The elements are serialized as list, starting with the head
+impl<'a, T: Serialize> Serialize for Seq<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut list = serializer.serialize_seq(Some(self.len()))?;
+        for ft in self {
+            list.serialize_element(ft)?;
+        }
+        list.end()
+    }
+}
+
+/// A serialization adapter, serializing the elements starting with the bottom element
+#[cfg(feature = "alloc")]
+pub struct OldestFirst<'a, T: 'a> {
+    seq: &'a Seq<'a, T>,
+}
+
+/// The serialization adapter method
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns a serialization adapter, serializing the elements in chronological order, the
+    /// head being the last one. The element references are buffered.
+    ///
+    /// ```rust
+    /// use seq::Seq;
+    ///
+    /// let s1 = Seq::ConsRef("main", seq::empty());
+    /// let s2 = Seq::ConsRef("run", &s1);
+    /// assert_eq!(serde_json::to_string(&s2.oldest_first()).unwrap(), r#"["main","run"]"#);
+    /// ```
+    pub fn oldest_first(&'a self) -> OldestFirst<'a, T> {
+        OldestFirst { seq: self }
+    }
+}
+
+/// The elements are serialized as list, starting with the bottom element
+#[cfg(feature = "alloc")]
+impl<'a, T: Serialize> Serialize for OldestFirst<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.seq.rev_iter())
+    }
+}
+
+/// Serializes the elements in chronological order, to be used as
+/// `#[serde(serialize_with = "seq::ser::serialize_oldest_first")]`
+#[cfg(feature = "alloc")]
+pub fn serialize_oldest_first<'a, T, S>(seq: &Seq<'a, T>, serializer: S) -> Result<S::Ok, S::Error>
+    where T: Serialize, S: Serializer
+{
+    serializer.collect_seq(seq.rev_iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{empty, Seq};
+    #[cfg(feature = "alloc")]
+    use super::serialize_oldest_first;
+    #[cfg(feature = "alloc")]
+    use std::vec::Vec;
+
+    #[test]
+    fn test_serialize() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        assert_eq!(serde_json::to_string(&s).unwrap(), "[3,2,1]");
+        assert_eq!(serde_json::to_string(empty::<u32>()).unwrap(), "[]");
+
+        let data = [4u32, 5];
+        let sliced = Seq::ConsSlice(&data, &s);
+        assert_eq!(serde_json::to_string(&sliced).unwrap(), "[5,4,3,2,1]");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_serialize_oldest_first() {
+        seqdef!(s; empty() => 1u32, 2, 3);
+
+        assert_eq!(serde_json::to_string(&s.oldest_first()).unwrap(), "[1,2,3]");
+
+        let mut out = Vec::new();
+        serialize_oldest_first(&s, &mut serde_json::Serializer::new(&mut out)).unwrap();
+        assert_eq!(out, b"[1,2,3]");
+    }
+}
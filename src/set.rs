@@ -0,0 +1,141 @@
+//! Persistent sets, the members held by a sequence.
+//!
+//! A [`SeqSet`] holds each member once, the one inserted last first. Inserting attaches the
+//! member in front, borrowing the set beneath, so a recursive traversal tracks the nodes
+//! visited on its path without mutation and without heap allocation; the callers' sets are
+//! unchanged on return. Lookups walk the members, in O(n).
+//!
+//! # Example
+//! ```rust
+//! use seq::set::SeqSet;
+//!
+//! // the depth of the graph, following each path until a node repeats
+//! fn depth(edges: &[(u32, u32)], node: u32, visited: &SeqSet<u32>) -> usize {
+//!     if visited.contains(&node) {
+//!         return 0;
+//!     }
+//!     let visited = visited.insert(node);
+//!     edges.iter().filter(|e| e.0 == node)
+//!         .map(|e| 1 + depth(edges, e.1, &visited))
+//!         .max().unwrap_or(0)
+//! }
+//!
+//! let edges = [(0, 1), (1, 2), (2, 0), (1, 3)];
+//! assert_eq!(depth(&edges, 0, &SeqSet::new()), 3);
+//! ```
+//! [`SeqSet`]: struct.SeqSet.html
+
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+use alloc::boxed::Box;
+use core::borrow::Borrow;
+
+use super::{Seq, SeqIterator};
+
+/// A set of members, held once each by a sequence
+pub struct SeqSet<'a, T: 'a> {
+    seq: Seq<'a, T>,
+}
+
+/// SeqSet method implementations
+impl<'a, T: 'a> SeqSet<'a, T> {
+    /// Returns the empty set
+    pub const fn new() -> Self {
+        SeqSet { seq: Seq::Empty }
+    }
+
+    /// Returns the sequence of members, the one inserted last first
+    pub fn as_seq(&self) -> &Seq<'a, T> {
+        &self.seq
+    }
+
+    /// Returns true if `value` is a member
+    pub fn contains<Q>(&'a self, value: &Q) -> bool
+        where T: Borrow<Q>, Q: Eq + ?Sized
+    {
+        self.seq.into_iter().any(|ft| ft.borrow() == value)
+    }
+
+    /// Returns a new set with `value` inserted in front of this one; if already a member, the
+    /// new set references this one, holding no element of its own
+    pub fn insert(&'a self, value: T) -> SeqSet<'a, T>
+        where T: Eq
+    {
+        if self.contains(&value) {
+            SeqSet { seq: Seq::ConsSlice(&[], &self.seq) }
+        } else {
+            SeqSet { seq: Seq::ConsRef(value, &self.seq) }
+        }
+    }
+
+    /// Returns the iterator over the members, the one inserted last first
+    pub fn iter(&'a self) -> SeqIterator<'a, T> {
+        self.seq.into_iter()
+    }
+
+    /// Returns the number of members
+    pub fn len(&'a self) -> usize {
+        self.seq.len()
+    }
+
+    /// Returns true if the set has no members
+    pub fn is_empty(&'a self) -> bool {
+        self.seq.is_empty()
+    }
+}
+
+/// SeqSet union method implementations
+#[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+impl<'a, T: Clone + Eq + 'a> SeqSet<'a, T> {
+    /// Returns the union of this set and `other`; the members of `other` missing in this set are
+    /// cloned into boxed nodes in front of this set, which is shared
+    pub fn union_owned(&'a self, other: &SeqSet<'_, T>) -> SeqSet<'a, T> {
+        let mut seq = Seq::ConsSlice(&[], &self.seq);
+        for ft in other.seq.into_iter() {
+            if !self.contains(ft) {
+                seq = Seq::ConsOwn(ft.clone(), Box::new(seq));
+            }
+        }
+        SeqSet { seq }
+    }
+}
+
+impl<'a, T: 'a> Default for SeqSet<'a, T> {
+    fn default() -> Self {
+        SeqSet::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SeqSet;
+
+    #[test]
+    fn test_insert_contains() {
+        let s0 = SeqSet::new();
+        let s1 = s0.insert(1u32);
+        let s2 = s1.insert(2);
+        let s3 = s2.insert(1);
+        assert!(s3.contains(&1) && s3.contains(&2));
+        assert!(!s1.contains(&2));
+        assert_eq!(s3.len(), 2);
+        assert!(s3.iter().eq([2, 1].iter()));
+        assert!(s0.is_empty());
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "lite-seq")))]
+    #[test]
+    fn test_union_owned() {
+        let a0 = SeqSet::new();
+        let a1 = a0.insert(1u32);
+        let a2 = a1.insert(2);
+        let b0 = SeqSet::new();
+        let b1 = b0.insert(2u32);
+        let b2 = b1.insert(3);
+        let b3 = b2.insert(4);
+
+        let u = a2.union_owned(&b3);
+        assert_eq!(u.len(), 4);
+        assert!(u.iter().eq([3, 4, 2, 1].iter()));
+        assert_eq!(a2.union_owned(&a2).len(), 2);
+    }
+}
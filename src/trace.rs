@@ -0,0 +1,131 @@
+//! Span context stacks of `tracing`, enabled by feature `tracing`.
+//!
+//! Recursive code keeps the spans it entered on a sequence, each frame entering its span by
+//! [`in_span`] and borrowing the sequence of its caller, without heap allocation per frame. The
+//! formatting adapters [`SpanNames`] and [`ContextDisplay`] render the stack root first, as
+//! value of a `tracing` field; the macro [`seq_event!`] attaches it as field `context` to an
+//! event.
+//!
+//! # Example
+//! ```rust
+//! extern crate tracing;
+//! use seq::Seq;
+//! use seq::trace::{in_span, SpanNames};
+//! use tracing::{info_span, Level, Span};
+//!
+//! fn walk(ctx: &Seq<Span>, depth: u32) -> String {
+//!     if depth == 0 {
+//!         return SpanNames::new(ctx).to_string();
+//!     }
+//!     in_span(ctx, info_span!("walk", depth), |ctx| walk(ctx, depth - 1))
+//! }
+//!
+//! // without subscriber the spans are disabled, rendered by their placeholder
+//! assert_eq!(walk(seq::empty(), 2), "? > ?");
+//! ```
+//! [`in_span`]: fn.in_span.html
+//! [`SpanNames`]: struct.SpanNames.html
+//! [`ContextDisplay`]: struct.ContextDisplay.html
+//! [`seq_event!`]: ../macro.seq_event.html
+
+use core::fmt;
+
+use tracing::Span;
+
+use super::{fmt_root_first, Seq};
+
+/// The separator printed between two entries of a context stack
+pub const CONTEXT_STACK_SEPARATOR: &str = " > ";
+
+/// The name printed of a span without metadata, being disabled
+pub const DISABLED_SPAN_NAME: &str = "?";
+
+/// Calls `f` with the span context extended by `span`, the span being entered while `f` runs
+pub fn in_span<'a, R, F>(ctx: &'a Seq<'a, Span>, span: Span, f: F) -> R
+    where F: FnOnce(&Seq<'_, Span>) -> R
+{
+    let ctx = Seq::ConsRef(span, ctx);
+    let _guard = ctx.head().map(Span::enter);
+    f(&ctx)
+}
+
+/// A formatting adapter printing the entries of a context stack, root first
+pub struct ContextDisplay<'a, T: 'a> {
+    seq: &'a Seq<'a, T>,
+    separator: &'a str,
+}
+
+/// ContextDisplay method implementations
+impl<'a, T: 'a> ContextDisplay<'a, T> {
+    /// Returns the adapter printing `seq`, separated by [`CONTEXT_STACK_SEPARATOR`]
+    ///
+    /// [`CONTEXT_STACK_SEPARATOR`]: constant.CONTEXT_STACK_SEPARATOR.html
+    pub fn new(seq: &'a Seq<'a, T>) -> Self {
+        ContextDisplay { seq, separator: CONTEXT_STACK_SEPARATOR }
+    }
+
+    /// Sets the separator printed between two entries
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+}
+
+/// The entries are printed root first, separated by the separator of the adapter
+impl<'a, T: fmt::Display> fmt::Display for ContextDisplay<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_root_first(self.seq, self.separator, f, |ft, f| fmt::Display::fmt(ft, f))
+    }
+}
+
+/// A formatting adapter printing the names of the spans of a context stack, root first
+pub struct SpanNames<'a> {
+    seq: &'a Seq<'a, Span>,
+}
+
+/// SpanNames method implementations
+impl<'a> SpanNames<'a> {
+    /// Returns the adapter printing the names of the spans of `seq`
+    pub fn new(seq: &'a Seq<'a, Span>) -> Self {
+        SpanNames { seq }
+    }
+}
+
+/// The names are printed root first, separated by [`CONTEXT_STACK_SEPARATOR`]; disabled spans
+/// are printed as [`DISABLED_SPAN_NAME`]
+///
+/// [`CONTEXT_STACK_SEPARATOR`]: constant.CONTEXT_STACK_SEPARATOR.html
+/// [`DISABLED_SPAN_NAME`]: constant.DISABLED_SPAN_NAME.html
+impl<'a> fmt::Display for SpanNames<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_root_first(self.seq, CONTEXT_STACK_SEPARATOR, f, |span: &Span, f| {
+            f.write_str(span.metadata().map_or(DISABLED_SPAN_NAME, |meta| meta.name()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{in_span, ContextDisplay, SpanNames};
+    use super::super::{empty, Seq};
+    use tracing::Span;
+
+    #[test]
+    fn test_context_display() {
+        seqdef!(s; empty() => "main", "parse", "expr");
+        assert_eq!(format!("{}", ContextDisplay::new(&s)), "main > parse > expr");
+        assert_eq!(format!("{}", ContextDisplay::new(&s).separator("/")), "main/parse/expr");
+        assert_eq!(format!("{}", ContextDisplay::new(empty::<&str>())), "");
+    }
+
+    #[test]
+    fn test_in_span() {
+        let depth = in_span(empty(), Span::none(), |ctx| {
+            in_span(ctx, Span::none(), |ctx: &Seq<Span>| ctx.len())
+        });
+        assert_eq!(depth, 2);
+        let ctx = Seq::ConsRef(Span::none(), empty());
+        assert_eq!(format!("{}", SpanNames::new(&ctx)), "?");
+    }
+
+}
@@ -0,0 +1,127 @@
+//! Views of a sequence, projecting each element onto one of its fields.
+//!
+//! A view borrows the underlying sequence, no element is copied and no memory is allocated.
+//!
+//! # Example
+//! ```rust
+//! use seq::Seq;
+//!
+//! struct Frame { name: &'static str, depth: usize }
+//!
+//! fn names<'a>(frames: &'a Seq<'a, Frame>) -> Vec<&'a str> {
+//!    frames.project_view(|frame| &frame.name).into_iter().cloned().collect()
+//! }
+//! ```
+
+use core::iter::Map;
+
+use super::Seq;
+use super::SeqIterator;
+
+/// A view of the sequence of type `T`, presenting the field selected by the projection `F`
+/// of each element
+pub struct ProjectView<'a, T: 'a, F> {
+    seq: &'a Seq<'a, T>,
+    proj: F,
+}
+
+/// The projection method
+impl<'a, T: 'a> Seq<'a, T> {
+    /// Returns a view of the sequence, presenting the field selected by `proj` of each element
+    pub fn project_view<U: 'a, F>(&'a self, proj: F) -> ProjectView<'a, T, F>
+        where F: Fn(&'a T) -> &'a U
+    {
+        ProjectView { seq: self, proj }
+    }
+}
+
+/// ProjectView method implementations
+impl<'a, T: 'a, U: 'a, F> ProjectView<'a, T, F>
+    where F: Fn(&'a T) -> &'a U
+{
+    /// Returns a reference to the projected head-element
+    pub fn head(&self) -> Option<&'a U> {
+        self.seq.head().map(&self.proj)
+    }
+
+    /// Returns the view of the tail
+    pub fn tail(&self) -> Option<ProjectView<'a, T, F>>
+        where F: Clone
+    {
+        self.seq.tail().map(|rt| ProjectView { seq: rt, proj: self.proj.clone() })
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.seq.len()
+    }
+
+    /// Returns true if the view is empty
+    pub fn is_empty(&self) -> bool {
+        self.seq.is_empty()
+    }
+
+    /// Returns the underlying sequence
+    pub fn as_seq(&self) -> &'a Seq<'a, T> {
+        self.seq
+    }
+
+    /// Returns an iterator over the projected elements
+    pub fn iter(&self) -> Map<SeqIterator<'a, T>, &F> {
+        self.seq.into_iter().map(&self.proj)
+    }
+}
+
+/// A view implements the `IntoIterator` trait, yielding the projected elements
+impl<'a, T: 'a, U: 'a, F> IntoIterator for ProjectView<'a, T, F>
+    where F: Fn(&'a T) -> &'a U
+{
+    type Item = &'a U;
+    type IntoIter = Map<SeqIterator<'a, T>, F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.seq.into_iter().map(self.proj)
+    }
+}
+
+/// A view reference implements the `IntoIterator` trait, yielding the projected elements
+impl<'s, 'a, T: 'a, U: 'a, F> IntoIterator for &'s ProjectView<'a, T, F>
+    where F: Fn(&'a T) -> &'a U
+{
+    type Item = &'a U;
+    type IntoIter = Map<SeqIterator<'a, T>, &'s F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+    use super::super::Seq;
+    use super::super::empty;
+
+    struct Frame {
+        name: &'static str,
+        depth: u32,
+    }
+
+    #[test]
+    fn test_project_view() {
+        let s0: &Seq<Frame> = empty();
+        let s1 = Seq::ConsRef(Frame { name: "main", depth: 0 }, s0);
+        let s2 = Seq::ConsRef(Frame { name: "run", depth: 1 }, &s1);
+
+        let names = s2.project_view(|frame| &frame.name);
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.head(), Some(&"run"));
+        assert_eq!(names.tail().unwrap().head(), Some(&"main"));
+
+        let collected: Vec<&str> = names.iter().cloned().collect();
+        assert_eq!(collected, vec!["run", "main"]);
+
+        let depths: u32 = s2.project_view(|frame| &frame.depth).into_iter().sum();
+        assert_eq!(depths, 1);
+    }
+}